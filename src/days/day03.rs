@@ -1,7 +1,7 @@
 use crate::{Solution, SolutionPair};
 use grid::Grid;
 use itertools::Itertools;
-use regex::{Match, Regex};
+use regex::Regex;
 
 #[derive(Clone, PartialEq)]
 
@@ -1,33 +1,170 @@
+use crate::etc::parse::{blocks, labelled, lines, number_row, parse_all};
 use crate::{Solution, SolutionPair};
+use nom::{character::complete::line_ending, sequence::preceded};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day5.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// A single "destination source length" line from a map
+struct Mapping {
+    destination: i64,
+    source: i64,
+    length: i64,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+struct Almanac {
+    seeds: Vec<i64>,
+    // The seven maps in order, each sorted by source start
+    maps: Vec<Vec<Mapping>>,
+}
+
+fn parse_almanac(input: &str) -> Almanac {
+    let (seeds, maps) = input.split_once("\n\n").expect("missing seed list");
+    let (_, seeds) = parse_all(seeds, labelled(number_row)).unwrap();
+
+    // Each map block is "x-to-y map:" followed by a line per mapping
+    let maps = parse_all(
+        maps,
+        blocks(labelled(preceded(line_ending, lines(number_row)))),
+    )
+    .unwrap()
+    .into_iter()
+    .map(|(_, rows)| {
+        let mut map = rows
+            .iter()
+            .map(|row| Mapping {
+                destination: row[0],
+                source: row[1],
+                length: row[2],
+            })
+            .collect::<Vec<Mapping>>();
+        map.sort_by_key(|m| m.source);
+        map
+    })
+    .collect();
+
+    Almanac { seeds, maps }
+}
+
+// Push a list of [start, end) ranges through one map, splitting them wherever a mapping starts or stops
+fn map_ranges(ranges: Vec<(i64, i64)>, map: &[Mapping]) -> Vec<(i64, i64)> {
+    let mut result = vec![];
+
+    for (mut start, end) in ranges {
+        for m in map {
+            if start >= end {
+                break;
+            }
+            let source_end = m.source + m.length;
+            if source_end <= start || m.source >= end {
+                continue;
+            }
+
+            // Anything before this mapping isn't covered by the map so it passes through unchanged
+            if start < m.source {
+                result.push((start, m.source));
+                start = m.source;
+            }
+
+            let overlap_end = end.min(source_end);
+            let offset = m.destination - m.source;
+            result.push((start + offset, overlap_end + offset));
+            start = overlap_end;
+        }
+
+        // Whatever's left after the last mapping also passes straight through
+        if start < end {
+            result.push((start, end));
+        }
+    }
+
+    result
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn lowest_location(almanac: &Almanac, seeds: Vec<(i64, i64)>) -> i64 {
+    almanac
+        .maps
+        .iter()
+        .fold(seeds, |ranges, map| map_ranges(ranges, map))
+        .iter()
+        .map(|(start, _)| *start)
+        .min()
+        .unwrap()
+}
+
+fn part_a(input: &str) -> i64 {
+    let almanac = parse_almanac(input);
+    // Every seed is a range of length one
+    let seeds = almanac.seeds.iter().map(|&s| (s, s + 1)).collect();
+    lowest_location(&almanac, seeds)
+}
+
+fn part_b(input: &str) -> i64 {
+    let almanac = parse_almanac(input);
+    // The seeds are pairs of start and length
+    let seeds = almanac
+        .seeds
+        .chunks(2)
+        .map(|pair| (pair[0], pair[0] + pair[1]))
+        .collect();
+    lowest_location(&almanac, seeds)
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 35);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 46);
     }
 }
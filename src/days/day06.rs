@@ -1,33 +1,90 @@
+use crate::etc::math::quadratic_below_zero;
+use crate::etc::parse::{labelled, lines, number_row, parse_all};
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day6.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Returns the times and record distances
+fn parse_races(input: &str) -> (Vec<i64>, Vec<i64>) {
+    let rows = parse_all(input, lines(labelled(number_row))).unwrap();
+    (rows[0].1.clone(), rows[1].1.clone())
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+// Holding the button for t leaves (time - t) to travel at speed t, so we win when t * (time - t) > record.
+// That's t^2 - time * t + record < 0, so count the whole numbers between the roots
+fn ways_to_win(time: i64, record: i64) -> i64 {
+    quadratic_below_zero(1, -time, record).map_or(0, |range| range.end() - range.start() + 1)
+}
+
+fn part_a(input: &str) -> i64 {
+    let (times, records) = parse_races(input);
+    times
+        .iter()
+        .zip(records.iter())
+        .map(|(&time, &record)| ways_to_win(time, record))
+        .product::<i64>()
+}
+
+fn part_b(input: &str) -> i64 {
+    let (times, records) = parse_races(input);
+    // The spaces were a lie, glue all the digits back together into one race
+    let join = |values: Vec<i64>| values.iter().join("").parse::<i64>().unwrap();
+    ways_to_win(join(times), join(records))
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200
+";
+
+    // Try every possible hold time
+    fn brute_force(time: i64, record: i64) -> i64 {
+        (0..=time).filter(|t| t * (time - t) > record).count() as i64
+    }
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 288);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 71503);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for time in 0..60 {
+            for record in 0..(time * time / 4 + 2) {
+                assert_eq!(ways_to_win(time, record), brute_force(time, record));
+            }
+        }
+        // Exact boundaries where the record can only be tied
+        assert_eq!(ways_to_win(30, 225), 0);
+        assert_eq!(ways_to_win(30, 224), 1);
+        assert_eq!(ways_to_win(71530, 940200), brute_force(71530, 940200));
+        // A 15 digit record where f64 roots would start losing precision. Too many hold times to try them all, so
+        // check the winning range stops exactly where the distance stops beating the record
+        let (time, record) = (61_677_571, 430_103_613_071_150);
+        let distance = |t: i64| t * (time - t);
+        let range = quadratic_below_zero(1, -time, record).unwrap();
+        let (start, end) = (*range.start(), *range.end());
+        assert!(distance(start - 1) <= record && distance(start) > record);
+        assert!(distance(end) > record && distance(end + 1) <= record);
+        assert_eq!(ways_to_win(time, record), end - start + 1);
     }
 }
@@ -1,33 +1,162 @@
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day7.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Declared weakest first so the derived ordering ranks them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl HandType {
+    // Work out the type from how many of each card there are, biggest group first
+    fn from_counts(counts: &[u8]) -> HandType {
+        match counts {
+            [5] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+struct Hand {
+    cards: [char; 5],
+    bid: u64,
+}
+
+impl Hand {
+    fn parse(line: &str) -> Hand {
+        let (cards, bid) = line.split_once(' ').unwrap();
+        Hand {
+            cards: cards.chars().collect_vec().try_into().unwrap(),
+            bid: bid.parse::<u64>().unwrap(),
+        }
+    }
+}
+
+// How many of each card there are, biggest group first
+fn group_sizes<'a>(cards: impl Iterator<Item = &'a char>) -> Vec<u8> {
+    cards
+        .counts()
+        .into_values()
+        .map(|count| count as u8)
+        .sorted()
+        .rev()
+        .collect_vec()
+}
+
+// How to rank hands, the two parts only differ in these rules
+trait Rules {
+    const ORDER: &'static str;
+
+    fn hand_type(hand: &Hand) -> HandType;
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+    // Compare by type first, then card by card from the left
+    fn sort_key(hand: &Hand) -> (HandType, [usize; 5]) {
+        let strengths = hand.cards.map(|c| Self::ORDER.find(c).unwrap());
+        (Self::hand_type(hand), strengths)
+    }
+}
+
+struct Standard;
+
+impl Rules for Standard {
+    const ORDER: &'static str = "23456789TJQKA";
+
+    fn hand_type(hand: &Hand) -> HandType {
+        HandType::from_counts(&group_sizes(hand.cards.iter()))
+    }
+}
+
+struct Jokers;
+
+impl Rules for Jokers {
+    // Jokers are the weakest card on their own
+    const ORDER: &'static str = "J23456789TQKA";
+
+    fn hand_type(hand: &Hand) -> HandType {
+        // The best thing a joker can do is join the biggest group of other cards
+        let mut counts = group_sizes(hand.cards.iter().filter(|&&c| c != 'J'));
+        let jokers = 5 - counts.iter().sum::<u8>();
+        if counts.is_empty() {
+            return HandType::FiveOfAKind;
+        }
+        counts[0] += jokers;
+        HandType::from_counts(&counts)
+    }
+}
+
+fn total_winnings<R: Rules>(input: &str) -> u64 {
+    input
+        .lines()
+        .map(Hand::parse)
+        .sorted_by_cached_key(R::sort_key)
+        .enumerate()
+        .map(|(rank, hand)| (rank as u64 + 1) * hand.bid)
+        .sum::<u64>()
+}
+
+fn part_a(input: &str) -> u64 {
+    total_winnings::<Standard>(input)
+}
+
+fn part_b(input: &str) -> u64 {
+    total_winnings::<Jokers>(input)
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 6440);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 5905);
+    }
+
+    #[test]
+    fn test_hand_types() {
+        let hand_type = |cards: &str| Jokers::hand_type(&Hand::parse(&format!("{cards} 0")));
+        assert_eq!(hand_type("JJJJJ"), HandType::FiveOfAKind);
+        assert_eq!(hand_type("JJJJ2"), HandType::FiveOfAKind);
+        assert_eq!(hand_type("2345J"), HandType::OnePair);
+        assert_eq!(hand_type("2233J"), HandType::FullHouse);
+        assert_eq!(hand_type("KTJJT"), HandType::FourOfAKind);
+        assert_eq!(
+            Standard::hand_type(&Hand::parse("KTJJT 0")),
+            HandType::TwoPair
+        );
     }
 }
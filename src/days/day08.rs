@@ -1,33 +1,291 @@
+use crate::etc::cycle::find_cycle;
+use crate::etc::math::{crt, lcm_all, CrtError};
+use crate::etc::parse::{key_pair_map, parse_all};
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
+use std::collections::HashMap;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day8.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Nodes are stored by index, with the left and right exits pointing at other indexes
+struct Network<'a> {
+    instructions: Vec<char>,
+    names: Vec<&'a str>,
+    exits: Vec<(usize, usize)>,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl<'a> Network<'a> {
+    fn parse(input: &'a str) -> Network<'a> {
+        let (instructions, nodes) = input.split_once("\n\n").unwrap();
+        let map = parse_all(nodes, key_pair_map).unwrap();
+
+        let names = map.keys().copied().sorted().collect_vec();
+        let index: HashMap<&str, usize> = names.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let exits = names
+            .iter()
+            .map(|name| {
+                let (left, right) = map[name];
+                (index[left], index[right])
+            })
+            .collect();
+
+        Network {
+            instructions: instructions.trim().chars().collect(),
+            names,
+            exits,
+        }
+    }
+
+    fn find(&self, name: &str) -> usize {
+        self.names.iter().position(|&n| n == name).unwrap()
+    }
+
+    // Take a single step, the state is the current node and where we are in the instructions
+    fn step(&self, (node, i): (usize, usize)) -> (usize, usize) {
+        let (left, right) = self.exits[node];
+        let next = if self.instructions[i] == 'L' {
+            left
+        } else {
+            right
+        };
+        (next, (i + 1) % self.instructions.len())
+    }
+}
+
+// Where a ghost ends up on a Z node. Hits before the loop starts only happen once, the rest repeat every length steps
+struct GhostCycle {
+    offset: usize,
+    length: usize,
+    prefix_hits: Vec<usize>,
+    cycle_hits: Vec<usize>,
+}
+
+impl GhostCycle {
+    fn analyse(network: &Network, start: usize) -> GhostCycle {
+        let cycle = find_cycle((start, 0), |&state| network.step(state));
+        let (prefix_hits, cycle_hits) = cycle
+            .states()
+            .iter()
+            .positions(|&(node, _)| network.names[node].ends_with('Z'))
+            .partition(|&t| t < cycle.prefix);
+
+        GhostCycle {
+            offset: cycle.prefix,
+            length: cycle.length,
+            prefix_hits,
+            cycle_hits,
+        }
+    }
+
+    fn is_z_at(&self, t: usize) -> bool {
+        if t < self.offset {
+            return self.prefix_hits.contains(&t);
+        }
+        let t = self.offset + (t - self.offset) % self.length;
+        self.cycle_hits.contains(&t)
+    }
+}
+
+// Find the first step where every ghost is on a Z at once
+fn combine(ghosts: &[GhostCycle]) -> Result<u64, String> {
+    // The usual shape, each ghost hits exactly one Z at a multiple of its loop length. The lcm only counts if
+    // every ghost has reached its loop by then, otherwise a ghost with a long lead in could still be outside it
+    let settled = ghosts.iter().map(|g| g.offset).max().unwrap();
+    if ghosts.iter().all(|g| {
+        g.prefix_hits.is_empty() && g.cycle_hits.len() == 1 && g.cycle_hits[0] % g.length == 0
+    }) {
+        let lcm = lcm_all(ghosts.iter().map(|g| g.length as u64));
+        if lcm >= settled as u64 {
+            return Ok(lcm);
+        }
+    }
+
+    // Check for any answers before every ghost has settled into its loop
+    if let Some(t) = (0..settled).find(|&t| ghosts.iter().all(|g| g.is_z_at(t))) {
+        return Ok(t as u64);
+    }
+
+    let summary = || {
+        ghosts
+            .iter()
+            .map(|g| {
+                format!(
+                    "(offset {}, length {}, hits {:?})",
+                    g.offset, g.length, g.cycle_hits
+                )
+            })
+            .join(", ")
+    };
+
+    // Otherwise try every combination of one Z per ghost and solve them with the chinese remainder theorem
+    let combinations = ghosts
+        .iter()
+        .map(|g| {
+            g.cycle_hits
+                .iter()
+                .map(|&hit| (hit as i64, g.length as i64))
+        })
+        .multi_cartesian_product();
+
+    let mut best: Option<u64> = None;
+    for congruences in combinations {
+        let (t, modulus) = match crt(&congruences) {
+            Ok(solution) => solution,
+            Err(CrtError::Contradiction) => continue,
+            // Can't rule this combination out, so any answer from the others might not be the first
+            Err(CrtError::Overflow) => {
+                return Err(format!(
+                    "The ghosts' loops combine into a period too big for an i64: {}",
+                    summary()
+                ))
+            }
+        };
+
+        // Bump the answer up until everyone is in their loop
+        let (t, settled) = (t as u64, settled as u64);
+        let t = if t >= settled {
+            t
+        } else {
+            t + (settled - t).div_ceil(modulus as u64) * modulus as u64
+        };
+        best = Some(best.map_or(t, |best| best.min(t)));
+    }
+
+    best.ok_or_else(|| {
+        format!(
+            "The ghosts are never all on a Z node at the same time: {}",
+            summary()
+        )
+    })
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_a(input: &str) -> u64 {
+    let network = Network::parse(input);
+    let end = network.find("ZZZ");
+
+    let mut state = (network.find("AAA"), 0);
+    let mut steps = 0;
+    while state.0 != end {
+        state = network.step(state);
+        steps += 1;
+    }
+    steps
+}
+
+fn part_b(input: &str) -> u64 {
+    let network = Network::parse(input);
+    let ghosts = network
+        .names
+        .iter()
+        .positions(|name| name.ends_with('A'))
+        .map(|start| GhostCycle::analyse(&network, start))
+        .collect_vec();
+
+    combine(&ghosts).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT_1: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    static TEST_INPUT_2: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    static TEST_INPUT_3: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    // Ghost 1 is on a Z at every even step, ghost 2 at steps 1, 4, 7, ...
+    static OFFSET_INPUT: &str = "L
+
+1A = (1B, 1B)
+1B = (1Z, 1Z)
+1Z = (1B, 1B)
+2A = (2Z, 2Z)
+2Z = (2C, 2C)
+2C = (2D, 2D)
+2D = (2Z, 2Z)
+";
+
+    // A single ghost that takes 4 steps to reach a loop of length 2, the loop length alone would say 2
+    static LONG_PREFIX_INPUT: &str = "L
+
+1A = (1B, 1B)
+1B = (1C, 1C)
+1C = (1D, 1D)
+1D = (1Z, 1Z)
+1Z = (1E, 1E)
+1E = (1Z, 1Z)
+";
+
+    // Ghost 1 is on a Z at every even step and ghost 3 at every odd step
+    static IMPOSSIBLE_INPUT: &str = "L
+
+1A = (1B, 1B)
+1B = (1Z, 1Z)
+1Z = (1B, 1B)
+3A = (3Z, 3Z)
+3Z = (3B, 3B)
+3B = (3Z, 3Z)
+";
 
     #[test]
     fn test_part_a() {
-        let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(part_a(TEST_INPUT_1), 2);
+        assert_eq!(part_a(TEST_INPUT_2), 6);
     }
 
     #[test]
     fn test_part_b() {
-        let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        let result = part_b(TEST_INPUT_3);
+        assert_eq!(result, 6);
+    }
+
+    #[test]
+    fn test_crt_fallback() {
+        // Plain lcm would say 6 here
+        assert_eq!(part_b(OFFSET_INPUT), 4);
+    }
+
+    #[test]
+    fn test_lcm_before_loop() {
+        assert_eq!(part_b(LONG_PREFIX_INPUT), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "never all on a Z node")]
+    fn test_no_solution() {
+        part_b(IMPOSSIBLE_INPUT);
     }
 }
@@ -1,33 +1,86 @@
+use crate::etc::parse::{lines, number_row, parse_all};
 use crate::{Solution, SolutionPair};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day9.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Each sequence of n values is a polynomial of degree below n, so the next value follows straight from the
+// binomial expansion of the nth difference being zero: a[n] = sum of (-1)^(n - 1 - i) * C(n, i) * a[i]
+fn extrapolate(values: &[i64]) -> i64 {
+    let n = values.len() as i128;
+    let mut binomial: i128 = 1;
+    let mut result: i128 = 0;
+
+    for (i, &value) in values.iter().enumerate() {
+        let i = i as i128;
+        let sign = if (n - 1 - i) % 2 == 0 { 1 } else { -1 };
+        result += sign * binomial * value as i128;
+        // Move from C(n, i) on to C(n, i + 1)
+        binomial = binomial * (n - i) / (i + 1);
+    }
+
+    result as i64
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+fn part_a(input: &str) -> i64 {
+    parse_all(input, lines(number_row))
+        .unwrap()
+        .iter()
+        .map(|values| extrapolate(values))
+        .sum::<i64>()
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_b(input: &str) -> i64 {
+    // Extrapolating backwards is the same as extrapolating the reversed sequence forwards
+    parse_all(input, lines(number_row))
+        .unwrap()
+        .into_iter()
+        .map(|mut values| {
+            values.reverse();
+            extrapolate(&values)
+        })
+        .sum::<i64>()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 114);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // Constant sequences stay constant, including a lone value
+        assert_eq!(extrapolate(&[7, 7, 7, 7]), 7);
+        assert_eq!(extrapolate(&[-3]), -3);
+        // Negative numbers in both directions
+        assert_eq!(extrapolate(&[-1, -4, -9, -16]), -25);
+        assert_eq!(extrapolate(&[5, 1, -3, -7]), -11);
+        assert_eq!(part_b("-25 -16 -9 -4 -1 0"), -36);
+        // A cubic over a full length input line
+        let cubic = (0..21).map(|x: i64| x * x * x - 40 * x).collect::<Vec<_>>();
+        assert_eq!(extrapolate(&cubic), 21 * 21 * 21 - 40 * 21);
     }
 }
@@ -1,33 +1,221 @@
+use crate::etc::parse::{grid, parse_all};
+use crate::etc::polygon::interior_points;
 use crate::{Solution, SolutionPair};
+use grid::Grid;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day10.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Offsets as (row, column)
+const NORTH: (isize, isize) = (-1, 0);
+const SOUTH: (isize, isize) = (1, 0);
+const EAST: (isize, isize) = (0, 1);
+const WEST: (isize, isize) = (0, -1);
+
+// The two directions each pipe connects
+fn connections(tile: char) -> &'static [(isize, isize)] {
+    match tile {
+        '|' => &[NORTH, SOUTH],
+        '-' => &[EAST, WEST],
+        'L' => &[NORTH, EAST],
+        'J' => &[NORTH, WEST],
+        '7' => &[SOUTH, WEST],
+        'F' => &[SOUTH, EAST],
+        _ => &[],
+    }
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+fn neighbour(
+    grid: &Grid<char>,
+    (row, col): (usize, usize),
+    (dr, dc): (isize, isize),
+) -> Option<(usize, usize)> {
+    let row = row.checked_add_signed(dr)?;
+    let col = col.checked_add_signed(dc)?;
+    grid.get(row, col).map(|_| (row, col))
+}
+
+// Follow the pipes round from the start, returning every tile on the way in order if they lead back to it
+fn follow(grid: &Grid<char>, start: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+    let mut path = vec![start];
+    let mut position = start;
+    let mut direction = connections(grid[start])[0];
+
+    loop {
+        position = neighbour(grid, position, direction)?;
+        // Whatever we step onto has to connect back the way we came
+        let back = (-direction.0, -direction.1);
+        let pipe = connections(grid[position]);
+        if !pipe.contains(&back) {
+            return None;
+        }
+        if position == start {
+            return Some(path);
+        }
+        path.push(position);
+        // Leave by whichever side of the pipe we didn't come in from
+        direction = *pipe.iter().find(|&&d| d != back).unwrap();
+    }
+}
+
+// Work out which pipe is hiding under the S. Stray pipes can point at it too, so try every pipe whose ends both
+// connect to a neighbour and keep the one that leads all the way round
+fn infer_start(grid: &Grid<char>, start: (usize, usize)) -> char {
+    let mut grid = grid.clone();
+    "|-LJ7F"
+        .chars()
+        .find(|&tile| {
+            grid[start] = tile;
+            follow(&grid, start).is_some()
+        })
+        .expect("S isn't part of a loop")
+}
+
+// Parse the maze, replace the S with its real pipe and follow the loop round, returning every tile on it in order
+fn find_loop(input: &str) -> Vec<(usize, usize)> {
+    let mut grid = parse_all(input, grid).unwrap();
+    let start = grid
+        .indexed_iter()
+        .find(|(_, &tile)| tile == 'S')
+        .map(|(position, _)| position)
+        .expect("no S in the maze");
+    grid[start] = infer_start(&grid, start);
+    follow(&grid, start).unwrap()
+}
+
+fn part_a(input: &str) -> usize {
+    // The furthest point is half way round the loop
+    find_loop(input).len() / 2
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_b(input: &str) -> i64 {
+    // Treat the loop as a polygon, Pick's theorem then counts the tiles inside.
+    // Squeezing between pipes doesn't matter as we never look at the tiles themselves
+    let vertices = find_loop(input)
+        .iter()
+        .map(|&(row, col)| (col as i64, row as i64))
+        .collect::<Vec<_>>();
+    interior_points(&vertices)
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static SIMPLE_LOOP: &str = ".....
+.S-7.
+.|.|.
+.L-J.
+.....
+";
+
+    static SIMPLE_LOOP_NOISY: &str = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF
+";
+
+    // The pipe above the S points at it but isn't part of the loop
+    static STRAY_PIPE: &str = ".|...
+.S-7.
+.|.|.
+.L-J.
+.....
+";
+
+    static COMPLEX_LOOP: &str = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    static COMPLEX_LOOP_NOISY: &str = "7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ
+";
+
+    static ENCLOSED: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    static SQUEEZED: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+
+    static LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    static JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
 
     #[test]
     fn test_part_a() {
-        let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(part_a(SIMPLE_LOOP), 4);
+        assert_eq!(part_a(SIMPLE_LOOP_NOISY), 4);
+        assert_eq!(part_a(STRAY_PIPE), 4);
+        assert_eq!(part_a(COMPLEX_LOOP), 8);
+        assert_eq!(part_a(COMPLEX_LOOP_NOISY), 8);
     }
 
     #[test]
     fn test_part_b() {
-        let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(part_b(SIMPLE_LOOP), 1);
+        assert_eq!(part_b(ENCLOSED), 4);
+        assert_eq!(part_b(SQUEEZED), 4);
+        assert_eq!(part_b(LARGER), 8);
+        assert_eq!(part_b(JUNK), 10);
+    }
+
+    #[test]
+    fn test_infer_start() {
+        let maze = parse_all(COMPLEX_LOOP, grid).unwrap();
+        assert_eq!(infer_start(&maze, (2, 0)), 'F');
+        let maze = parse_all(JUNK, grid).unwrap();
+        assert_eq!(infer_start(&maze, (0, 4)), '7');
+        let maze = parse_all(STRAY_PIPE, grid).unwrap();
+        assert_eq!(infer_start(&maze, (1, 1)), 'F');
     }
 }
@@ -1,33 +1,101 @@
 use crate::{Solution, SolutionPair};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day11.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// The distances split into rows and columns, so each axis can be handled on its own.
+// Takes how many galaxies are in each row (or column) and returns the sum of distances between every pair along that axis
+fn axis_distance_sum(counts: &[u64], factor: u64) -> u64 {
+    let mut position = 0;
+    // How many galaxies we've passed so far and the sum of their positions
+    let mut seen = 0;
+    let mut position_sum = 0;
+    let mut total = 0;
+
+    for &count in counts {
+        if count == 0 {
+            // Empty lines grow by the expansion factor
+            position += factor;
+            continue;
+        }
+
+        // Each galaxy here is (position - p) away from every earlier galaxy at p
+        total += count * (seen * position - position_sum);
+        seen += count;
+        position_sum += count * position;
+        position += 1;
+    }
+
+    total
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+fn distance_sum(input: &str, factor: u64) -> u64 {
+    let width = input.lines().next().map_or(0, |line| line.len());
+    let mut rows = vec![];
+    let mut cols = vec![0; width];
+
+    for line in input.lines() {
+        let mut row = 0;
+        for (col, c) in line.chars().enumerate() {
+            if c == '#' {
+                row += 1;
+                cols[col] += 1;
+            }
+        }
+        rows.push(row);
+    }
+
+    axis_distance_sum(&rows, factor) + axis_distance_sum(&cols, factor)
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_a(input: &str) -> u64 {
+    distance_sum(input, 2)
+}
+
+fn part_b(input: &str) -> u64 {
+    distance_sum(input, 1_000_000)
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 374);
+    }
+
+    #[test]
+    fn test_expansion_factors() {
+        assert_eq!(distance_sum(TEST_INPUT, 10), 1030);
+        assert_eq!(distance_sum(TEST_INPUT, 100), 8410);
+        // No expansion at all is just the plain manhattan distances
+        assert_eq!(distance_sum(TEST_INPUT, 1), 292);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 82000210);
     }
 }
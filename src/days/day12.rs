@@ -1,33 +1,136 @@
+use crate::etc::memo::Memo;
 use crate::{Solution, SolutionPair};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day12.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+struct Record {
+    springs: Vec<u8>,
+    groups: Vec<usize>,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl Record {
+    fn parse(line: &str) -> Record {
+        let (springs, groups) = line.split_once(' ').unwrap();
+        Record {
+            springs: springs.bytes().collect(),
+            groups: groups
+                .split(',')
+                .map(|n| n.parse::<usize>().unwrap())
+                .collect(),
+        }
+    }
+
+    // Five copies of the springs joined by '?' and five copies of the groups
+    fn unfold(&self) -> Record {
+        Record {
+            springs: [self.springs.as_slice(); 5].join(&b'?'),
+            groups: self.groups.repeat(5),
+        }
+    }
+
+    fn arrangements(&self) -> u64 {
+        // A fresh cache per record, what's left of each slice is enough to identify the state
+        let mut memo = Memo::new();
+        count(&mut memo, &self.springs, &self.groups)
+    }
+}
+
+// Count the ways the remaining springs can be filled in to match the remaining groups
+fn count(memo: &mut Memo<(usize, usize), u64>, springs: &[u8], groups: &[usize]) -> u64 {
+    let Some(&group) = groups.first() else {
+        // Everything's placed, so there can't be any more damaged springs
+        return u64::from(!springs.contains(&b'#'));
+    };
+    if springs.len() < groups.iter().sum::<usize>() + groups.len() - 1 {
+        return 0;
+    }
+
+    memo.get_or_insert_with((springs.len(), groups.len()), |memo| {
+        let mut total = 0;
+
+        // Treat the first spring as working
+        if springs[0] != b'#' {
+            total += count(memo, &springs[1..], groups);
+        }
+
+        // Or start the next group here, which needs room for the whole group and no damaged spring straight after it
+        if springs[0] != b'.'
+            && !springs[..group].contains(&b'.')
+            && springs.get(group) != Some(&b'#')
+        {
+            let rest = springs.get(group + 1..).unwrap_or(&[]);
+            total += count(memo, rest, &groups[1..]);
+        }
+
+        total
+    })
+}
+
+fn part_a(input: &str) -> u64 {
+    input
+        .lines()
+        .map(|line| Record::parse(line).arrangements())
+        .sum::<u64>()
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_b(input: &str) -> u64 {
+    input
+        .lines()
+        .map(|line| Record::parse(line).unfold().arrangements())
+        .sum::<u64>()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 21);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 525152);
+    }
+
+    #[test]
+    fn test_rows() {
+        let folded = TEST_INPUT
+            .lines()
+            .map(|line| Record::parse(line).arrangements())
+            .collect::<Vec<_>>();
+        assert_eq!(folded, vec![1, 4, 1, 1, 4, 10]);
+
+        let unfolded = TEST_INPUT
+            .lines()
+            .map(|line| Record::parse(line).unfold().arrangements())
+            .collect::<Vec<_>>();
+        assert_eq!(unfolded, vec![1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn test_unfold() {
+        let record = Record::parse(".# 1").unfold();
+        assert_eq!(record.springs, b".#?.#?.#?.#?.#");
+        assert_eq!(record.groups, vec![1; 5]);
     }
 }
@@ -1,33 +1,122 @@
+use crate::etc::parse::{blocks, grid, parse_all};
 use crate::{Solution, SolutionPair};
+use grid::Grid;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day13.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Each row and column packed into bits with rocks as 1s, so patterns can be at most 64 wide and tall
+struct Pattern {
+    rows: Vec<u64>,
+    cols: Vec<u64>,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl Pattern {
+    fn from_grid(grid: &Grid<char>) -> Pattern {
+        assert!(
+            grid.rows() <= 64 && grid.cols() <= 64,
+            "pattern is too big to pack into a u64"
+        );
+        let pack = |line: &mut dyn Iterator<Item = &char>| {
+            line.fold(0, |bits, &c| (bits << 1) | (c == '#') as u64)
+        };
+
+        Pattern {
+            rows: (0..grid.rows())
+                .map(|r| pack(&mut grid.iter_row(r)))
+                .collect(),
+            cols: (0..grid.cols())
+                .map(|c| pack(&mut grid.iter_col(c)))
+                .collect(),
+        }
+    }
+
+    // Columns to the left of a vertical line plus 100 times the rows above a horizontal one
+    fn summarise(&self, smudges: u32) -> usize {
+        if let Some(cols) = find_reflection(&self.cols, smudges) {
+            return cols;
+        }
+        find_reflection(&self.rows, smudges).expect("no line of reflection") * 100
+    }
+}
+
+// Find the line (given as the number of lines before it) where the two sides differ by exactly `smudges` cells
+fn find_reflection(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&split| {
+        // Walk outwards from the line, stopping at whichever edge is closest
+        lines[..split]
+            .iter()
+            .rev()
+            .zip(lines[split..].iter())
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum::<u32>()
+            == smudges
+    })
+}
+
+fn parse_patterns(input: &str) -> Vec<Pattern> {
+    parse_all(input, blocks(grid))
+        .unwrap()
+        .iter()
+        .map(Pattern::from_grid)
+        .collect()
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_a(input: &str) -> usize {
+    parse_patterns(input).iter().map(|p| p.summarise(0)).sum()
+}
+
+fn part_b(input: &str) -> usize {
+    // The smudge means the real reflection is off by exactly one cell
+    parse_patterns(input).iter().map(|p| p.summarise(1)).sum()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 405);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 400);
+    }
+
+    #[test]
+    fn test_each_pattern() {
+        let patterns = parse_patterns(TEST_INPUT);
+        assert_eq!(find_reflection(&patterns[0].cols, 0), Some(5));
+        assert_eq!(find_reflection(&patterns[1].rows, 0), Some(4));
+        assert_eq!(find_reflection(&patterns[0].rows, 1), Some(3));
+        assert_eq!(find_reflection(&patterns[1].rows, 1), Some(1));
     }
 }
@@ -1,33 +1,219 @@
+use crate::etc::cycle::fast_forward;
 use crate::{Solution, SolutionPair};
+use std::fmt::{Display, Formatter};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day14.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+#[derive(Clone, Copy)]
+enum Direction {
+    North,
+    West,
+    South,
+    East,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Platform {
+    tiles: Vec<Vec<u8>>,
+}
+
+impl Platform {
+    fn parse(input: &str) -> Platform {
+        Platform {
+            tiles: input.lines().map(|line| line.bytes().collect()).collect(),
+        }
+    }
+
+    // Every row or column, listed starting from the edge the rocks are rolling towards
+    fn lanes(&self, direction: Direction) -> Vec<Vec<(usize, usize)>> {
+        let (rows, cols) = (self.tiles.len(), self.tiles[0].len());
+        match direction {
+            Direction::North => (0..cols)
+                .map(|c| (0..rows).map(|r| (r, c)).collect())
+                .collect(),
+            Direction::South => (0..cols)
+                .map(|c| (0..rows).rev().map(|r| (r, c)).collect())
+                .collect(),
+            Direction::West => (0..rows)
+                .map(|r| (0..cols).map(|c| (r, c)).collect())
+                .collect(),
+            Direction::East => (0..rows)
+                .map(|r| (0..cols).rev().map(|c| (r, c)).collect())
+                .collect(),
+        }
+    }
+
+    fn tilt(&mut self, direction: Direction) {
+        for lane in self.lanes(direction) {
+            // Where the next round rock will come to rest
+            let mut free = 0;
+            for (i, &(r, c)) in lane.iter().enumerate() {
+                match self.tiles[r][c] {
+                    b'#' => free = i + 1,
+                    b'O' => {
+                        let (fr, fc) = lane[free];
+                        self.tiles[r][c] = b'.';
+                        self.tiles[fr][fc] = b'O';
+                        free += 1;
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+    fn spin(&self) -> Platform {
+        let mut platform = self.clone();
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            platform.tilt(direction);
+        }
+        platform
+    }
+
+    // Each round rock adds its distance from the south edge
+    fn north_load(&self) -> usize {
+        let rows = self.tiles.len();
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(|(r, row)| row.iter().filter(|&&t| t == b'O').count() * (rows - r))
+            .sum()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in &self.tiles {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+fn part_a(input: &str) -> usize {
+    let mut platform = Platform::parse(input);
+    platform.tilt(Direction::North);
+    platform.north_load()
+}
+
+fn part_b(input: &str) -> usize {
+    // The platform settles into a loop long before a billion spins, so skip ahead once it repeats
+    fast_forward(Platform::parse(input), Platform::spin, 1_000_000_000).north_load()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 136);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 64);
+    }
+
+    #[test]
+    fn test_tilt_north() {
+        let mut platform = Platform::parse(TEST_INPUT);
+        platform.tilt(Direction::North);
+        assert_eq!(
+            platform.to_string(),
+            "OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+"
+        );
+    }
+
+    #[test]
+    fn test_spin_cycles() {
+        let after_one = Platform::parse(TEST_INPUT).spin();
+        assert_eq!(
+            after_one.to_string(),
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+        );
+
+        let after_two = after_one.spin();
+        assert_eq!(
+            after_two.to_string(),
+            ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#..OO###..
+#.OOO#...O
+"
+        );
+
+        let after_three = after_two.spin();
+        assert_eq!(
+            after_three.to_string(),
+            ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O
+"
+        );
+        assert_eq!(
+            fast_forward(Platform::parse(TEST_INPUT), Platform::spin, 3),
+            after_three
+        );
     }
 }
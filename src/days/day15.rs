@@ -1,33 +1,152 @@
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day15.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+fn hash(step: &str) -> usize {
+    step.bytes()
+        .fold(0, |acc, c| ((acc + c as usize) * 17) % 256)
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+// The steps are comma separated, newlines are ignored
+fn steps(input: &str) -> impl Iterator<Item = String> + '_ {
+    input
+        .split(',')
+        .map(|step| step.chars().filter(|&c| c != '\n' && c != '\r').collect())
+}
+
+// Each box holds its lenses in the order they went in
+struct Boxes<'a> {
+    boxes: Vec<Vec<(&'a str, u8)>>,
+}
+
+impl<'a> Boxes<'a> {
+    fn new() -> Boxes<'a> {
+        Boxes {
+            boxes: vec![vec![]; 256],
+        }
+    }
+
+    // Either "label=focal length" to add or replace a lens, or "label-" to take it out
+    fn apply(&mut self, step: &'a str) {
+        if let Some(label) = step.strip_suffix('-') {
+            self.boxes[hash(label)].retain(|(l, _)| *l != label);
+            return;
+        }
+
+        let (label, focal) = step.split_once('=').unwrap();
+        let focal = focal.parse::<u8>().unwrap();
+        let lenses = &mut self.boxes[hash(label)];
+        match lenses.iter_mut().find(|(l, _)| *l == label) {
+            Some(lens) => lens.1 = focal,
+            None => lenses.push((label, focal)),
+        }
+    }
+
+    fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(b, lenses)| {
+                lenses
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, (_, focal))| (b + 1) * (slot + 1) * *focal as usize)
+            })
+            .sum()
+    }
+}
+
+// The non-empty boxes in the same format as the puzzle, e.g. "Box 0: [rn 1] [cm 2]"
+impl Display for Boxes<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let dump = self
+            .boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, lenses)| !lenses.is_empty())
+            .map(|(b, lenses)| {
+                let lenses = lenses
+                    .iter()
+                    .map(|(label, focal)| format!("[{label} {focal}]"))
+                    .join(" ");
+                format!("Box {b}: {lenses}")
+            })
+            .join("\n");
+        write!(f, "{dump}")
+    }
+}
+
+fn part_a(input: &str) -> usize {
+    steps(input).map(|step| hash(&step)).sum()
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_b(input: &str) -> usize {
+    let steps = steps(input).collect_vec();
+    let mut boxes = Boxes::new();
+    for step in &steps {
+        boxes.apply(step);
+    }
+    boxes.focusing_power()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
 
     #[test]
     fn test_part_a() {
+        assert_eq!(hash("HASH"), 52);
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 1320);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 145);
+    }
+
+    #[test]
+    fn test_newlines_ignored() {
+        assert_eq!(
+            part_a("rn=1,c\nm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"),
+            1320
+        );
+    }
+
+    #[test]
+    fn test_box_contents() {
+        let expected = [
+            "Box 0: [rn 1]",
+            "Box 0: [rn 1]",
+            "Box 0: [rn 1]\nBox 1: [qp 3]",
+            "Box 0: [rn 1] [cm 2]\nBox 1: [qp 3]",
+            "Box 0: [rn 1] [cm 2]",
+            "Box 0: [rn 1] [cm 2]\nBox 3: [pc 4]",
+            "Box 0: [rn 1] [cm 2]\nBox 3: [pc 4] [ot 9]",
+            "Box 0: [rn 1] [cm 2]\nBox 3: [pc 4] [ot 9] [ab 5]",
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 9] [ab 5]",
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 9] [ab 5] [pc 6]",
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]",
+        ];
+
+        let steps = steps(TEST_INPUT).collect_vec();
+        let mut boxes = Boxes::new();
+        for (step, expected) in steps.iter().zip(expected) {
+            boxes.apply(step);
+            assert_eq!(boxes.to_string(), expected, "after {step}");
+        }
     }
 }
@@ -1,33 +1,126 @@
+use crate::etc::parse::{grid, parse_all};
 use crate::{Solution, SolutionPair};
+use grid::Grid;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day16.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Directions are stored as bit flags so every direction a tile has been crossed in fits in one u8
+const UP: u8 = 1;
+const RIGHT: u8 = 2;
+const DOWN: u8 = 4;
+const LEFT: u8 = 8;
+
+// Which way(s) the beam leaves a tile when entering it heading in the given direction
+fn exits(tile: char, direction: u8) -> &'static [u8] {
+    match (tile, direction) {
+        ('/', RIGHT) | ('\\', LEFT) => &[UP],
+        ('/', LEFT) | ('\\', RIGHT) => &[DOWN],
+        ('/', UP) | ('\\', DOWN) => &[RIGHT],
+        ('/', DOWN) | ('\\', UP) => &[LEFT],
+        ('|', LEFT | RIGHT) => &[UP, DOWN],
+        ('-', UP | DOWN) => &[LEFT, RIGHT],
+        (_, UP) => &[UP],
+        (_, RIGHT) => &[RIGHT],
+        (_, DOWN) => &[DOWN],
+        _ => &[LEFT],
+    }
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+fn step(grid: &Grid<char>, (row, col): (usize, usize), direction: u8) -> Option<(usize, usize)> {
+    let (row, col) = match direction {
+        UP => (row.checked_sub(1)?, col),
+        DOWN => (row + 1, col),
+        LEFT => (row, col.checked_sub(1)?),
+        _ => (row, col + 1),
+    };
+    grid.get(row, col).map(|_| (row, col))
+}
+
+// Follow the beam (and every split of it) from the start, counting the tiles it passes through.
+// A beam entering a tile in a direction it's been seen going before is already being followed, which stops loops
+fn energized(grid: &Grid<char>, start: (usize, usize), direction: u8) -> usize {
+    let mut seen: Grid<u8> = Grid::new(grid.rows(), grid.cols());
+    let mut beams = vec![(start, direction)];
+
+    while let Some((position, direction)) = beams.pop() {
+        if seen[position] & direction != 0 {
+            continue;
+        }
+        seen[position] |= direction;
+
+        for &exit in exits(grid[position], direction) {
+            if let Some(next) = step(grid, position, exit) {
+                beams.push((next, exit));
+            }
+        }
+    }
+
+    seen.iter().filter(|&&directions| directions != 0).count()
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_a(input: &str) -> usize {
+    let grid = parse_all(input, grid).unwrap();
+    energized(&grid, (0, 0), RIGHT)
+}
+
+fn part_b(input: &str) -> usize {
+    let grid = parse_all(input, grid).unwrap();
+    let (rows, cols) = (grid.rows(), grid.cols());
+
+    // Every tile along each edge, heading into the grid
+    let starts = (0..rows)
+        .flat_map(|r| [((r, 0), RIGHT), ((r, cols - 1), LEFT)])
+        .chain((0..cols).flat_map(|c| [((0, c), DOWN), ((rows - 1, c), UP)]));
+
+    starts
+        .map(|(start, direction)| energized(&grid, start, direction))
+        .max()
+        .unwrap()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 46);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 51);
+    }
+
+    #[test]
+    fn test_loops() {
+        // The splitter sends the beam round the mirrors and back into itself forever, only loop detection stops this
+        let input = r".|.\
+....
+.\./
+";
+        assert_eq!(part_a(input), 9);
     }
 }
@@ -1,33 +1,215 @@
+use crate::etc::parse::{grid, parse_all};
 use crate::{Solution, SolutionPair};
+use grid::Grid;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{Display, Formatter};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day17.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Offsets as (row, column), turning left or right is a step either way around this list
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const ARROWS: [char; 4] = ['^', '>', 'v', '<'];
+
+// Where the crucible is, which way it's heading and how many blocks it's moved in a straight line
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct State {
+    row: usize,
+    col: usize,
+    direction: usize,
+    run: usize,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+// The cheapest way through, with each block entered and the direction the crucible was heading as it moved in
+struct Route<'a> {
+    grid: &'a Grid<u32>,
+    heat_loss: u32,
+    path: Vec<((usize, usize), usize)>,
+}
+
+// Draws the route over the map the same way the puzzle does
+impl Display for Route<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut map = Grid::from_vec(
+            self.grid
+                .iter()
+                .map(|&h| char::from_digit(h, 10).unwrap())
+                .collect(),
+            self.grid.cols(),
+        );
+        for &(position, direction) in &self.path {
+            map[position] = ARROWS[direction];
+        }
+        for row in map.iter_rows() {
+            writeln!(f, "{}", row.collect::<String>())?;
+        }
+        Ok(())
+    }
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn parse_map(input: &str) -> Grid<u32> {
+    let map = parse_all(input, grid).unwrap();
+    Grid::from_vec(
+        map.iter().map(|c| c.to_digit(10).unwrap()).collect(),
+        map.cols(),
+    )
+}
+
+// Dijkstra over (position, direction, run length). The crucible has to go at least min_run blocks before it can
+// turn or stop at the end, and can't go more than max_run in a straight line
+fn find_route(grid: &Grid<u32>, min_run: usize, max_run: usize) -> Route<'_> {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    let index = |s: &State| ((s.row * cols + s.col) * 4 + s.direction) * (max_run + 1) + s.run;
+
+    let mut best = vec![u32::MAX; rows * cols * 4 * (max_run + 1)];
+    let mut previous: Vec<Option<State>> = vec![None; best.len()];
+    let mut queue = BinaryHeap::new();
+
+    // Start off facing both ways out of the corner without having moved yet
+    for direction in [1, 2] {
+        let start = State {
+            row: 0,
+            col: 0,
+            direction,
+            run: 0,
+        };
+        best[index(&start)] = 0;
+        queue.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((heat_loss, state))) = queue.pop() {
+        if heat_loss > best[index(&state)] {
+            continue;
+        }
+
+        if (state.row, state.col) == (rows - 1, cols - 1) && state.run >= min_run {
+            // Walk the predecessors back to the start to recover the path
+            let mut path = vec![];
+            let mut current = state;
+            while current.run > 0 {
+                path.push(((current.row, current.col), current.direction));
+                current = previous[index(&current)].unwrap();
+            }
+            path.reverse();
+
+            return Route {
+                grid,
+                heat_loss,
+                path,
+            };
+        }
+
+        let mut moves = vec![];
+        if state.run < max_run {
+            moves.push((state.direction, state.run + 1));
+        }
+        if state.run >= min_run {
+            moves.push(((state.direction + 1) % 4, 1));
+            moves.push(((state.direction + 3) % 4, 1));
+        }
+
+        for (direction, run) in moves {
+            let (dr, dc) = DIRECTIONS[direction];
+            let (Some(row), Some(col)) = (
+                state.row.checked_add_signed(dr),
+                state.col.checked_add_signed(dc),
+            ) else {
+                continue;
+            };
+            if row >= rows || col >= cols {
+                continue;
+            }
+
+            let next = State {
+                row,
+                col,
+                direction,
+                run,
+            };
+            let cost = heat_loss + grid[(row, col)];
+            if cost < best[index(&next)] {
+                best[index(&next)] = cost;
+                previous[index(&next)] = Some(state);
+                queue.push(Reverse((cost, next)));
+            }
+        }
+    }
+
+    panic!("there's no way to reach the factory")
+}
+
+fn part_a(input: &str) -> u32 {
+    find_route(&parse_map(input), 1, 3).heat_loss
+}
+
+fn part_b(input: &str) -> u32 {
+    // Ultra crucibles
+    find_route(&parse_map(input), 4, 10).heat_loss
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    static ULTRA_INPUT: &str = "111111111111
+999999999991
+999999999991
+999999999991
+999999999991
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 102);
     }
 
     #[test]
     fn test_part_b() {
-        let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(part_b(TEST_INPUT), 94);
+        assert_eq!(part_b(ULTRA_INPUT), 71);
+    }
+
+    #[test]
+    fn test_route() {
+        let map = parse_map(ULTRA_INPUT);
+        let route = find_route(&map, 4, 10);
+        // The heat lost along the path adds up to the total
+        let total = route.path.iter().map(|&(p, _)| map[p]).sum::<u32>();
+        assert_eq!(total, route.heat_loss);
+
+        // It can't turn down at the end of the top row after 11 blocks, so it has to come down early
+        assert_eq!(
+            route.to_string(),
+            "1>>>>>>>1111
+9999999v9991
+9999999v9991
+9999999v9991
+9999999v>>>>
+"
+        );
     }
 }
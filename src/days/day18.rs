@@ -1,33 +1,90 @@
+use crate::etc::polygon::{lattice_points, vertices_from_moves};
 use crate::{Solution, SolutionPair};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day18.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+fn direction(c: char) -> (i64, i64) {
+    match c {
+        'R' | '0' => (1, 0),
+        'D' | '1' => (0, 1),
+        'L' | '2' => (-1, 0),
+        'U' | '3' => (0, -1),
+        _ => panic!("Not a valid direction: {c}"),
+    }
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+// The plan as written, e.g. "R 6 (#70c710)" is 6 to the right
+fn parse_plain(line: &str) -> ((i64, i64), i64) {
+    let mut parts = line.split_whitespace();
+    let dir = direction(parts.next().unwrap().chars().next().unwrap());
+    let distance = parts.next().unwrap().parse::<i64>().unwrap();
+    (dir, distance)
+}
+
+// The real plan hidden in the colour, the first five hex digits are the distance and the last is the direction
+fn parse_hex(line: &str) -> ((i64, i64), i64) {
+    let (_, colour) = line.split_once("(#").unwrap();
+    let colour = colour.trim_end_matches(')');
+    let distance = i64::from_str_radix(&colour[..5], 16).unwrap();
+    (direction(colour.chars().nth(5).unwrap()), distance)
+}
+
+// The trench runs through the middle of each dug cube, so the lagoon is every lattice point inside or on the polygon
+fn lagoon_size(input: &str, parse: fn(&str) -> ((i64, i64), i64)) -> i64 {
+    lattice_points(&vertices_from_moves(input.lines().map(parse)))
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+fn part_a(input: &str) -> i64 {
+    lagoon_size(input, parse_plain)
+}
+
+fn part_b(input: &str) -> i64 {
+    lagoon_size(input, parse_hex)
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 62);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 952408144115);
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("R 6 (#70c710)"), ((1, 0), 461937));
+        assert_eq!(parse_hex("U 2 (#7a21e3)"), ((0, -1), 500254));
     }
 }
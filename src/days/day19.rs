@@ -1,33 +1,228 @@
+use crate::etc::parse::{lines, parse_all};
 use crate::{Solution, SolutionPair};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending, one_of, u64},
+    combinator::map,
+    multi::{many0, separated_list1},
+    sequence::{delimited, pair, separated_pair, terminated, tuple},
+    IResult,
+};
+use std::collections::HashMap;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day19.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Index of each rating in a part
+fn category(c: char) -> usize {
+    "xmas".find(c).unwrap()
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+#[derive(Clone, Copy)]
+enum Target<'a> {
+    Accept,
+    Reject,
+    Workflow(&'a str),
+}
+
+struct Rule<'a> {
+    category: usize,
+    less_than: bool,
+    value: u64,
+    target: Target<'a>,
+}
+
+// Rules are checked in order, the fallback is used if none match
+struct Workflow<'a> {
+    rules: Vec<Rule<'a>>,
+    fallback: Target<'a>,
+}
+
+fn parse_target(input: &str) -> IResult<&str, Target<'_>> {
+    map(alpha1, |name| match name {
+        "A" => Target::Accept,
+        "R" => Target::Reject,
+        _ => Target::Workflow(name),
+    })(input)
+}
+
+fn parse_rule(input: &str) -> IResult<&str, Rule<'_>> {
+    let (input, (c, op, value, _, target)) =
+        tuple((one_of("xmas"), one_of("<>"), u64, tag(":"), parse_target))(input)?;
+    Ok((
+        input,
+        Rule {
+            category: category(c),
+            less_than: op == '<',
+            value,
+            target,
+        },
+    ))
+}
+
+// e.g. "px{a<2006:qkq,m>2090:A,rfg}"
+fn parse_workflow(input: &str) -> IResult<&str, (&str, Workflow<'_>)> {
+    let (input, name) = alpha1(input)?;
+    let (input, _) = tag("{")(input)?;
+    let (input, rules) = many0(terminated(parse_rule, tag(",")))(input)?;
+    let (input, fallback) = terminated(parse_target, tag("}"))(input)?;
+    Ok((input, (name, Workflow { rules, fallback })))
+}
+
+// e.g. "{x=787,m=2655,a=1222,s=2876}"
+fn parse_part(input: &str) -> IResult<&str, [u64; 4]> {
+    let (input, ratings) = delimited(
+        tag("{"),
+        separated_list1(tag(","), tuple((one_of("xmas"), tag("="), u64))),
+        tag("}"),
+    )(input)?;
+
+    let mut part = [0; 4];
+    for (c, _, value) in ratings {
+        part[category(c)] = value;
+    }
+    Ok((input, part))
+}
+
+// The workflows, a blank line, then the parts
+fn parse_input(input: &str) -> (HashMap<&str, Workflow<'_>>, Vec<[u64; 4]>) {
+    let (workflows, parts) = parse_all(
+        input,
+        separated_pair(
+            lines(parse_workflow),
+            pair(line_ending, line_ending),
+            lines(parse_part),
+        ),
+    )
+    .unwrap();
+    (workflows.into_iter().collect(), parts)
+}
+
+// Send a part through the workflows starting at "in" until it's accepted or rejected
+fn is_accepted(workflows: &HashMap<&str, Workflow>, part: &[u64; 4]) -> bool {
+    let mut name = "in";
+    loop {
+        let workflow = &workflows[name];
+        let target = workflow
+            .rules
+            .iter()
+            .find(|rule| {
+                let rating = part[rule.category];
+                if rule.less_than {
+                    rating < rule.value
+                } else {
+                    rating > rule.value
+                }
+            })
+            .map_or(workflow.fallback, |rule| rule.target);
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+        match target {
+            Target::Accept => return true,
+            Target::Reject => return false,
+            Target::Workflow(next) => name = next,
+        }
+    }
+}
+
+// Count every combination of ratings within the [low, high] ranges that ends up accepted.
+// Each rule cuts the box of ratings in two, the matching half follows the rule and the rest carries on
+fn count_accepted(
+    workflows: &HashMap<&str, Workflow>,
+    target: Target,
+    mut ranges: [(u64, u64); 4],
+) -> u64 {
+    let name = match target {
+        Target::Accept => return ranges.iter().map(|(low, high)| high - low + 1).product(),
+        Target::Reject => return 0,
+        Target::Workflow(name) => name,
+    };
+
+    let workflow = &workflows[name];
+    let mut total = 0;
+
+    for rule in &workflow.rules {
+        let (low, high) = ranges[rule.category];
+        let (matching, rest) = match (rule.less_than, rule.value.checked_sub(1)) {
+            (true, Some(below)) => ((low, high.min(below)), (low.max(rule.value), high)),
+            // Nothing is less than zero, so the whole range carries on
+            (true, None) => ((1, 0), (low, high)),
+            (false, _) => ((low.max(rule.value + 1), high), (low, high.min(rule.value))),
+        };
+
+        if matching.0 <= matching.1 {
+            let mut split = ranges;
+            split[rule.category] = matching;
+            total += count_accepted(workflows, rule.target, split);
+        }
+        if rest.0 > rest.1 {
+            return total;
+        }
+        ranges[rule.category] = rest;
+    }
+
+    total + count_accepted(workflows, workflow.fallback, ranges)
+}
+
+fn part_a(input: &str) -> u64 {
+    let (workflows, parts) = parse_input(input);
+    parts
+        .iter()
+        .filter(|part| is_accepted(&workflows, part))
+        .map(|part| part.iter().sum::<u64>())
+        .sum()
+}
+
+fn part_b(input: &str) -> u64 {
+    let (workflows, _) = parse_input(input);
+    count_accepted(&workflows, Target::Workflow("in"), [(1, 4000); 4])
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 19114);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 167409079868000);
+    }
+
+    #[test]
+    fn test_less_than_zero() {
+        let input = "in{x<0:R,A}\n\n{x=1,m=2,a=3,s=4}\n";
+        assert_eq!(part_a(input), 10);
+        assert_eq!(part_b(input), 4000_u64.pow(4));
     }
 }
@@ -1,33 +1,270 @@
+use crate::etc::math::lcm_all;
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
+use std::collections::{HashMap, VecDeque};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day20.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// Pulses from the button come from a module that doesn't exist
+const BUTTON: usize = usize::MAX;
+
+enum Kind {
+    Broadcaster,
+    // Whether it's currently on
+    FlipFlop(bool),
+    // The last pulse remembered from each input, in the same order as the module's inputs
+    Conjunction(Vec<bool>),
+    // Anything only ever mentioned as an output, like rx
+    Untyped,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+struct Module<'a> {
+    name: &'a str,
+    kind: Kind,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+struct Machine<'a> {
+    modules: Vec<Module<'a>>,
+    broadcaster: usize,
+}
+
+impl<'a> Machine<'a> {
+    fn parse(input: &'a str) -> Machine<'a> {
+        let lines = input
+            .lines()
+            .map(|line| line.split_once(" -> ").unwrap())
+            .collect_vec();
+
+        // Give every module an index, including the ones that only appear as outputs
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut modules = vec![];
+        let names = lines
+            .iter()
+            .map(|(module, _)| module.trim_start_matches(['%', '&']));
+        let outputs = lines.iter().flat_map(|(_, outputs)| outputs.split(", "));
+        for name in names.chain(outputs) {
+            index.entry(name).or_insert_with(|| {
+                modules.push(Module {
+                    name,
+                    kind: Kind::Untyped,
+                    inputs: vec![],
+                    outputs: vec![],
+                });
+                modules.len() - 1
+            });
+        }
+
+        for (module, outputs) in lines {
+            let name = module.trim_start_matches(['%', '&']);
+            let i = index[name];
+            modules[i].kind = match module.chars().next() {
+                Some('%') => Kind::FlipFlop(false),
+                Some('&') => Kind::Conjunction(vec![]),
+                _ => Kind::Broadcaster,
+            };
+            for output in outputs.split(", ") {
+                let o = index[output];
+                modules[i].outputs.push(o);
+                modules[o].inputs.push(i);
+            }
+        }
+
+        // Conjunctions start off remembering a low pulse from every input
+        for module in modules.iter_mut() {
+            if let Kind::Conjunction(memory) = &mut module.kind {
+                *memory = vec![false; module.inputs.len()];
+            }
+        }
+
+        let broadcaster = index["broadcaster"];
+        Machine {
+            modules,
+            broadcaster,
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name == name)
+    }
+
+    // Push the button once, handling pulses in the order they were sent. Every pulse is passed to on_pulse as
+    // (from, to, high) so the caller can count or watch for them
+    fn press<F: FnMut(usize, usize, bool)>(&mut self, mut on_pulse: F) {
+        let mut queue = VecDeque::from([(BUTTON, self.broadcaster, false)]);
+
+        while let Some((from, to, high)) = queue.pop_front() {
+            on_pulse(from, to, high);
+
+            let module = &mut self.modules[to];
+            let send = match &mut module.kind {
+                Kind::Broadcaster => Some(high),
+                // Flip flops ignore high pulses and toggle on a low one
+                Kind::FlipFlop(on) => {
+                    if high {
+                        None
+                    } else {
+                        *on = !*on;
+                        Some(*on)
+                    }
+                }
+                // Conjunctions send low only once every input's last pulse was high
+                Kind::Conjunction(memory) => {
+                    let slot = module.inputs.iter().position(|&i| i == from).unwrap();
+                    memory[slot] = high;
+                    Some(!memory.iter().all(|&m| m))
+                }
+                Kind::Untyped => None,
+            };
+
+            if let Some(pulse) = send {
+                for &output in &module.outputs {
+                    queue.push_back((to, output, pulse));
+                }
+            }
+        }
+    }
+}
+
+fn part_a(input: &str) -> u64 {
+    let mut machine = Machine::parse(input);
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..1000 {
+        machine.press(|_, _, pulse| {
+            if pulse {
+                high += 1;
+            } else {
+                low += 1;
+            }
+        });
+    }
+    low * high
+}
+
+// rx gets a low pulse when the conjunction in front of it sees a high pulse from every input on the same press.
+// Each of those inputs sends a high on a regular cycle, so find the cycles and line them up
+fn presses_for_rx(input: &str) -> Result<u64, String> {
+    let mut machine = Machine::parse(input);
+    let rx = machine.find("rx").ok_or("There's no rx module")?;
+
+    let feeder = match machine.modules[rx].inputs[..] {
+        [feeder] => feeder,
+        _ => return Err("rx needs to be fed by exactly one module".to_owned()),
+    };
+    if !matches!(machine.modules[feeder].kind, Kind::Conjunction(_)) {
+        return Err(format!(
+            "rx is fed by {} which isn't a conjunction",
+            machine.modules[feeder].name
+        ));
+    }
+
+    // The presses on which each input sent a high pulse to the feeder, we need two to measure the cycle
+    let inputs = machine.modules[feeder].inputs.clone();
+    let mut hits: Vec<Vec<u64>> = vec![vec![]; inputs.len()];
+    let mut presses = 0;
+
+    while hits.iter().any(|h| h.len() < 2) {
+        presses += 1;
+        if presses > 100_000 {
+            return Err(format!(
+                "Gave up waiting for every input of {} to cycle",
+                machine.modules[feeder].name
+            ));
+        }
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+        machine.press(|from, to, high| {
+            if to == feeder && high {
+                let slot = inputs.iter().position(|&i| i == from).unwrap();
+                if hits[slot].last() != Some(&presses) {
+                    hits[slot].push(presses);
+                }
+            }
+        });
+    }
+
+    // The lcm only works if each cycle starts from the very first press
+    if let Some((slot, h)) = hits.iter().find_position(|h| h[1] != 2 * h[0]) {
+        return Err(format!(
+            "{} sends high pulses on presses {} and {}, which isn't a cycle starting from zero",
+            machine.modules[inputs[slot]].name, h[0], h[1]
+        ));
+    }
+
+    Ok(lcm_all(hits.iter().map(|h| h[0])))
+}
+
+fn part_b(input: &str) -> u64 {
+    presses_for_rx(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT_1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    static TEST_INPUT_2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    // ca sends a high pulse to hub every 2 presses and cc every 4
+    static RX_INPUT: &str = "broadcaster -> a, b
+%a -> ca
+&ca -> hub
+%b -> c
+%c -> cc
+&cc -> hub
+&hub -> rx
+";
 
     #[test]
     fn test_part_a() {
-        let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(part_a(TEST_INPUT_1), 32000000);
+        assert_eq!(part_a(TEST_INPUT_2), 11687500);
     }
 
     #[test]
     fn test_part_b() {
-        let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(part_b(RX_INPUT), 4);
+
+        // Check against actually pressing the button
+        let mut machine = Machine::parse(RX_INPUT);
+        let rx = machine.find("rx").unwrap();
+        let mut presses = 0;
+        let mut done = false;
+        while !done {
+            presses += 1;
+            machine.press(|_, to, high| done |= to == rx && !high);
+        }
+        assert_eq!(presses, 4);
+    }
+
+    #[test]
+    fn test_wrong_shape() {
+        assert_eq!(
+            presses_for_rx(TEST_INPUT_2),
+            Err("There's no rx module".to_owned())
+        );
+        assert_eq!(
+            presses_for_rx("broadcaster -> a\n%a -> rx\n"),
+            Err("rx is fed by a which isn't a conjunction".to_owned())
+        );
     }
 }
@@ -1,33 +1,190 @@
+use crate::etc::parse::{grid, parse_all};
 use crate::{Solution, SolutionPair};
+use grid::Grid;
+use std::collections::{HashMap, VecDeque};
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day21.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+struct Garden {
+    rocks: Grid<bool>,
+    start: (usize, usize),
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl Garden {
+    fn parse(input: &str) -> Garden {
+        let map = parse_all(input, grid).unwrap();
+        let start = map
+            .indexed_iter()
+            .find(|(_, &c)| c == 'S')
+            .map(|(position, _)| position)
+            .expect("no S in the garden");
+
+        Garden {
+            rocks: Grid::from_vec(map.iter().map(|&c| c == '#').collect(), map.cols()),
+            start,
+        }
+    }
+
+    // BFS outwards from the start over the garden tiled infinitely in every direction, returning how many tiles
+    // are first reached after exactly d steps for each d up to max_steps
+    fn distance_counts(&self, max_steps: usize) -> Vec<u64> {
+        let (rows, cols) = (self.rocks.rows() as i64, self.rocks.cols() as i64);
+        // Tiles seen so far, kept per copy of the garden so only the copies actually reached take up any space
+        let mut seen: HashMap<(i64, i64), Vec<bool>> = HashMap::new();
+        let mut visit = |(r, c): (i64, i64)| {
+            let copy = (r.div_euclid(rows), c.div_euclid(cols));
+            let index = (r.rem_euclid(rows) * cols + c.rem_euclid(cols)) as usize;
+            let tiles = seen
+                .entry(copy)
+                .or_insert_with(|| vec![false; (rows * cols) as usize]);
+            !std::mem::replace(&mut tiles[index], true)
+        };
+
+        let start = (self.start.0 as i64, self.start.1 as i64);
+        let mut counts = vec![0; max_steps + 1];
+        let mut queue = VecDeque::from([(start, 0)]);
+        visit(start);
+
+        while let Some(((r, c), distance)) = queue.pop_front() {
+            counts[distance] += 1;
+            if distance == max_steps {
+                continue;
+            }
+
+            for (nr, nc) in [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)] {
+                // Wrap round into the original garden to check for rocks
+                let tile = (nr.rem_euclid(rows) as usize, nc.rem_euclid(cols) as usize);
+                if self.rocks[tile] || !visit((nr, nc)) {
+                    continue;
+                }
+                queue.push_back(((nr, nc), distance + 1));
+            }
+        }
+
+        counts
+    }
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+// A tile can be ended on after `steps` steps if it's reachable in fewer with the same parity, as the elf can
+// always step back and forth to use up the rest
+fn reachable(counts: &[u64], steps: usize) -> u64 {
+    counts[..=steps].iter().skip(steps % 2).step_by(2).sum()
+}
+
+// The most periods to sample before giving up. Each sample is a BFS out to r + k * period steps, which for a
+// 131 wide garden is already thousands of steps at this size
+const MAX_SAMPLES: usize = 12;
+
+// Once the BFS has spread across a few copies of the garden, the number reachable after r + k * period steps grows
+// as a quadratic in k. Sample it until the second differences settle down, then extrapolate to the number wanted
+fn infinite_reachable(garden: &Garden, steps: usize) -> u64 {
+    let size = garden.rocks.rows().max(garden.rocks.cols());
+
+    for period in [size, 2 * size] {
+        let remainder = steps % period;
+        let mut samples = 6;
+
+        while samples <= MAX_SAMPLES {
+            // Once a sample would need a BFS as far as the number wanted, counting directly is no more work
+            if remainder + samples * period >= steps {
+                return reachable(&garden.distance_counts(steps), steps);
+            }
+
+            let counts = garden.distance_counts(remainder + samples * period);
+            let values = (0..=samples)
+                .map(|k| reachable(&counts, remainder + k * period) as i64)
+                .collect::<Vec<_>>();
+            let second = values
+                .windows(3)
+                .map(|w| w[2] - 2 * w[1] + w[0])
+                .collect::<Vec<_>>();
+
+            // Look for the first sample after which the second difference stays the same
+            if let Some(k) =
+                (0..second.len() - 2).find(|&k| second[k..].iter().all(|&d| d == second[k]))
+            {
+                let (a, d1, d2) = (values[k], values[k + 1] - values[k], second[k]);
+                let n = ((steps - remainder) / period - k) as i64;
+                return (a + n * d1 + n * (n - 1) / 2 * d2) as u64;
+            }
+
+            samples *= 2;
+        }
+    }
+
+    panic!("No stable quadratic found for {steps} steps, the garden doesn't grow evenly");
+}
+
+fn part_a(input: &str) -> u64 {
+    let garden = Garden::parse(input);
+    reachable(&garden.distance_counts(64), 64)
+}
+
+fn part_b(input: &str) -> u64 {
+    infinite_reachable(&Garden::parse(input), 26_501_365)
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    // The step counts given in the puzzle for the infinite garden
+    const EXPECTED: [(usize, u64); 7] = [
+        (6, 16),
+        (10, 50),
+        (50, 1594),
+        (100, 6536),
+        (500, 167004),
+        (1000, 668697),
+        (5000, 16733044),
+    ];
 
     #[test]
     fn test_part_a() {
-        let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        let garden = Garden::parse(TEST_INPUT);
+        assert_eq!(reachable(&garden.distance_counts(6), 6), 16);
+    }
+
+    #[test]
+    fn test_brute_force() {
+        // Straight BFS over the infinite garden, the bigger counts take too long to do like this
+        let garden = Garden::parse(TEST_INPUT);
+        let counts = garden.distance_counts(500);
+        for &(steps, expected) in EXPECTED.iter().filter(|(steps, _)| *steps <= 500) {
+            assert_eq!(reachable(&counts, steps), expected, "after {steps} steps");
+        }
     }
 
     #[test]
     fn test_part_b() {
-        let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        let garden = Garden::parse(TEST_INPUT);
+        for (steps, expected) in EXPECTED {
+            assert_eq!(
+                infinite_reachable(&garden, steps),
+                expected,
+                "after {steps} steps"
+            );
+        }
     }
 }
@@ -1,33 +1,223 @@
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
+use std::collections::HashMap;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day22.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+// The two corners of a brick, (x, y, z) with the smaller values first
+#[derive(Clone, Copy)]
+struct Brick {
+    start: [usize; 3],
+    end: [usize; 3],
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl Brick {
+    fn parse(line: &str) -> Brick {
+        let (a, b) = line.split_once('~').unwrap();
+        let corner = |s: &str| -> [usize; 3] {
+            s.split(',')
+                .map(|n| n.parse::<usize>().unwrap())
+                .collect_vec()
+                .try_into()
+                .unwrap()
+        };
+        let (a, b) = (corner(a), corner(b));
+        Brick {
+            start: [0, 1, 2].map(|i| a[i].min(b[i])),
+            end: [0, 1, 2].map(|i| a[i].max(b[i])),
+        }
+    }
+
+    // Every (x, y) the brick covers when looking down from above
+    fn footprint(&self) -> impl Iterator<Item = (usize, usize)> {
+        (self.start[0]..=self.end[0]).cartesian_product(self.start[1]..=self.end[1])
+    }
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+// Which bricks each brick rests on, with the bricks in the order they settled (lowest first)
+struct Stack {
+    supported_by: Vec<Vec<usize>>,
+    supports: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    // Drop the bricks lowest first, keeping track of the highest brick at every (x, y)
+    fn settle(input: &str) -> Stack {
+        let bricks = input
+            .lines()
+            .map(Brick::parse)
+            .sorted_by_key(|b| b.start[2])
+            .collect_vec();
+
+        let mut heights: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut supported_by = vec![vec![]; bricks.len()];
+        let mut supports = vec![vec![]; bricks.len()];
+
+        for (i, brick) in bricks.iter().enumerate() {
+            let below = brick
+                .footprint()
+                .filter_map(|xy| heights.get(&xy).copied())
+                .collect_vec();
+            let rest_on = below.iter().map(|&(z, _)| z).max().unwrap_or(0);
+
+            // Whatever is at the resting height directly underneath is holding this brick up
+            for below in below
+                .iter()
+                .filter(|(z, _)| *z == rest_on)
+                .map(|&(_, b)| b)
+                .unique()
+            {
+                supported_by[i].push(below);
+                supports[below].push(i);
+            }
+
+            let top = rest_on + 1 + brick.end[2] - brick.start[2];
+            for xy in brick.footprint() {
+                heights.insert(xy, (top, i));
+            }
+        }
+
+        Stack {
+            supported_by,
+            supports,
+        }
+    }
+
+    // For each brick, the closest brick that everything holding it up relies on, i.e. its immediate dominator
+    // with the ground as the root. Removing a brick makes exactly the bricks below it in this tree fall
+    fn dominators(&self) -> Vec<Option<usize>> {
+        let n = self.supported_by.len();
+        let mut parent: Vec<Option<usize>> = vec![None; n];
+        let mut depth = vec![0; n];
+
+        // Bricks settled in order so everything under a brick already has its dominator worked out
+        for i in 0..n {
+            let mut supporters = self.supported_by[i].iter().copied();
+            let Some(first) = supporters.next() else {
+                // Resting on the ground
+                depth[i] = 1;
+                continue;
+            };
+
+            // Walk up the tree to the lowest common ancestor of all the supporters
+            let mut common = Some(first);
+            for other in supporters {
+                let mut a = common;
+                let mut b = Some(other);
+                while a != b {
+                    let depth_of = |x: Option<usize>| x.map_or(0, |x| depth[x]);
+                    if depth_of(a) >= depth_of(b) {
+                        a = a.and_then(|x| parent[x]);
+                    } else {
+                        b = b.and_then(|x| parent[x]);
+                    }
+                }
+                common = a;
+            }
+
+            parent[i] = common;
+            depth[i] = common.map_or(1, |c| depth[c] + 1);
+        }
+
+        parent
+    }
+}
+
+fn part_a(input: &str) -> usize {
+    let stack = Stack::settle(input);
+    // Safe if nothing it holds up depends on it alone
+    (0..stack.supports.len())
+        .filter(|&i| {
+            stack.supports[i]
+                .iter()
+                .all(|&above| stack.supported_by[above].len() > 1)
+        })
+        .count()
+}
+
+fn part_b(input: &str) -> usize {
+    let stack = Stack::settle(input);
+    let parent = stack.dominators();
+
+    // Count the bricks beneath each one in the dominator tree, children always come after their parents
+    let mut subtree = vec![1; parent.len()];
+    for i in (0..parent.len()).rev() {
+        if let Some(p) = parent[i] {
+            subtree[p] += subtree[i];
+        }
+    }
+    subtree.iter().map(|size| size - 1).sum()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    // Take out each brick in turn and follow the falls through the support graph
+    fn brute_force(input: &str) -> usize {
+        let stack = Stack::settle(input);
+        let n = stack.supported_by.len();
+        (0..n)
+            .map(|skip| {
+                // Bricks are in settled order, so supporters are always decided first
+                let mut fallen = vec![false; n];
+                fallen[skip] = true;
+                for i in 0..n {
+                    if i != skip
+                        && !stack.supported_by[i].is_empty()
+                        && stack.supported_by[i].iter().all(|&s| fallen[s])
+                    {
+                        fallen[i] = true;
+                    }
+                }
+                fallen.iter().filter(|&&f| f).count() - 1
+            })
+            .sum()
+    }
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 5);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 7);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        // Two towers joined by a bridge, with a slab balanced on two blocks sitting on the bridge
+        let input = "0,0,1~0,0,1
+3,0,1~3,0,1
+0,0,2~0,0,2
+3,0,2~3,0,2
+0,0,3~3,0,3
+1,0,4~1,0,4
+2,0,4~2,0,4
+1,0,5~2,0,5
+0,0,6~0,0,6
+";
+        assert_eq!(part_b(input), brute_force(input));
+        assert_eq!(part_b(TEST_INPUT), brute_force(TEST_INPUT));
     }
 }
@@ -1,33 +1,210 @@
+use crate::etc::parse::{grid, parse_all};
 use crate::{Solution, SolutionPair};
+use grid::Grid;
+use std::collections::HashMap;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day23.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+// The only direction a slope can be walked in
+fn slope_direction(tile: char) -> Option<(isize, isize)> {
+    match tile {
+        '^' => Some((-1, 0)),
+        '>' => Some((0, 1)),
+        'v' => Some((1, 0)),
+        '<' => Some((0, -1)),
+        _ => None,
+    }
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+fn neighbour(
+    map: &Grid<char>,
+    (row, col): (usize, usize),
+    (dr, dc): (isize, isize),
+) -> Option<(usize, usize)> {
+    let row = row.checked_add_signed(dr)?;
+    let col = col.checked_add_signed(dc)?;
+    match map.get(row, col) {
+        Some('#') | None => None,
+        Some(_) => Some((row, col)),
+    }
+}
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+// The trails squashed down to the junctions, with the length of each corridor between them
+struct Trails {
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+}
+
+impl Trails {
+    fn parse(input: &str, slippery: bool) -> Trails {
+        let map = parse_all(input, grid).unwrap();
+        let open_in_row = |row: usize| (0..map.cols()).find(|&c| map[(row, c)] == '.').unwrap();
+        let start = (0, open_in_row(0));
+        let end = (map.rows() - 1, open_in_row(map.rows() - 1));
+
+        // Junctions are anywhere with three or more ways out, plus the start and end
+        let mut junctions = vec![start, end];
+        junctions.extend(map.indexed_iter().filter_map(|(position, &tile)| {
+            let exits = DIRECTIONS
+                .iter()
+                .filter(|&&d| neighbour(&map, position, d).is_some())
+                .count();
+            (tile != '#' && exits >= 3).then_some(position)
+        }));
+        let index: HashMap<(usize, usize), usize> =
+            junctions.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+        assert!(
+            junctions.len() <= 64,
+            "too many junctions for a u64 bitmask"
+        );
+
+        // Follow each corridor out of every junction until it reaches the next one
+        let mut edges = vec![vec![]; junctions.len()];
+        for (from, &junction) in junctions.iter().enumerate() {
+            'corridor: for first in DIRECTIONS {
+                let mut previous = junction;
+                let mut direction = first;
+                let mut length = 0;
+
+                loop {
+                    let Some(position) = neighbour(&map, previous, direction) else {
+                        continue 'corridor;
+                    };
+                    // Slopes can only be walked down when they're slippery
+                    if slippery && slope_direction(map[position]).is_some_and(|d| d != direction) {
+                        continue 'corridor;
+                    }
+                    length += 1;
+
+                    if let Some(&to) = index.get(&position) {
+                        edges[from].push((to, length));
+                        continue 'corridor;
+                    }
+
+                    // A corridor only has the one way onwards that isn't back where we came from
+                    let back = (-direction.0, -direction.1);
+                    let Some(&next) = DIRECTIONS
+                        .iter()
+                        .find(|&&d| d != back && neighbour(&map, position, d).is_some())
+                    else {
+                        continue 'corridor;
+                    };
+                    previous = position;
+                    direction = next;
+                }
+            }
+        }
+
+        Trails {
+            edges,
+            start: 0,
+            end: 1,
+        }
+    }
+
+    fn longest_walk(&self) -> Option<usize> {
+        // The end can only be reached through one junction, and going anywhere else from there cuts it off
+        let (last, last_length) = self.edges.iter().enumerate().find_map(|(i, edges)| {
+            edges
+                .iter()
+                .find(|&&(to, _)| to == self.end)
+                .map(|&(_, length)| (i, length))
+        })?;
+        self.dfs(self.start, 1 << self.start, last)
+            .map(|length| length + last_length)
+    }
+
+    // The longest way from node to the last junction without visiting anywhere in the visited bitmask twice
+    fn dfs(&self, node: usize, visited: u64, last: usize) -> Option<usize> {
+        if node == last {
+            return Some(0);
+        }
+
+        self.edges[node]
+            .iter()
+            .filter(|&&(to, _)| visited & (1 << to) == 0)
+            .filter_map(|&(to, length)| {
+                self.dfs(to, visited | (1 << to), last)
+                    .map(|rest| rest + length)
+            })
+            .max()
+    }
+}
+
+fn part_a(input: &str) -> usize {
+    Trails::parse(input, true)
+        .longest_walk()
+        .expect("no way to the end")
+}
+
+fn part_b(input: &str) -> usize {
+    Trails::parse(input, false)
+        .longest_walk()
+        .expect("no way to the end")
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
 
     #[test]
     fn test_part_a() {
         let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 94);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 154);
+    }
+
+    #[test]
+    fn test_junctions() {
+        // Start, end and the seven junctions in between
+        let trails = Trails::parse(TEST_INPUT, false);
+        assert_eq!(trails.edges.len(), 9);
+        // Without slopes every corridor can be walked both ways
+        for (from, edges) in trails.edges.iter().enumerate() {
+            for &(to, length) in edges {
+                assert!(trails.edges[to].contains(&(from, length)));
+            }
+        }
     }
 }
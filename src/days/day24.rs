@@ -1,33 +1,230 @@
+use crate::etc::math::gcd_i128;
+use crate::etc::parse::{int_list, parse_all};
 use crate::{Solution, SolutionPair};
+use itertools::Itertools;
 
-fn part_a(_input: &str) -> u32 {
-    0
+fn get_input() -> &'static str {
+    include_str!("../../input/day24.txt")
 }
 
-fn part_b(_input: &str) -> u32 {
-    0
+type Vector = [i128; 3];
+
+#[derive(Clone, Copy)]
+struct Hailstone {
+    position: Vector,
+    velocity: Vector,
 }
 
-pub fn solve() -> SolutionPair {
-    // Your solution here...
+impl Hailstone {
+    // e.g. "19, 13, 30 @ -2,  1, -2"
+    fn parse(line: &str) -> Hailstone {
+        let (position, velocity) = line.split_once('@').unwrap();
+        let vector = |s: &str| -> Vector {
+            let values = parse_all(s.trim(), int_list(",")).unwrap();
+            [values[0] as i128, values[1] as i128, values[2] as i128]
+        };
+        Hailstone {
+            position: vector(position),
+            velocity: vector(velocity),
+        }
+    }
+}
+
+fn sub(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+// Whether the paths of two hailstones cross inside the test area going forwards in time, ignoring z.
+// Everything is kept as fractions over the same denominator so the comparisons are exact
+fn crosses_within(a: &Hailstone, b: &Hailstone, low: i128, high: i128) -> bool {
+    let (p, v) = (a.position, a.velocity);
+    let (q, w) = (b.position, b.velocity);
+
+    // Solve p + t * v = q + s * w, parallel paths never meet
+    let mut det = v[0] * w[1] - v[1] * w[0];
+    if det == 0 {
+        return false;
+    }
+    let d = sub(q, p);
+    let mut t = d[0] * w[1] - d[1] * w[0];
+    let mut s = d[0] * v[1] - d[1] * v[0];
+    if det < 0 {
+        (det, t, s) = (-det, -t, -s);
+    }
+
+    // Both need to be in the future, then the crossing point is p + (t / det) * v
+    if t < 0 || s < 0 {
+        return false;
+    }
+    (0..2).all(|i| {
+        let scaled = p[i] * det + t * v[i];
+        low * det <= scaled && scaled <= high * det
+    })
+}
+
+fn count_crossings(input: &str, low: i128, high: i128) -> usize {
+    input
+        .lines()
+        .map(Hailstone::parse)
+        .tuple_combinations()
+        .filter(|(a, b)| crosses_within(a, b, low, high))
+        .count()
+}
+
+// Work in the frame of the first hailstone, so it sits still at the origin and the rock has to pass through it.
+// The rock's path then lies in the plane through the origin and each other hailstone's path, so its direction
+// solves n1 . d = 0 and n2 . d = 0 for the two plane normals, which is d = n1 x n2.
+// Knowing the line, the times the other two hailstones cross it give the rock's speed and start
+fn throw_rock(hailstones: &[Hailstone]) -> (Vector, Vector) {
+    let origin = hailstones[0];
+    let relative = hailstones[1..]
+        .iter()
+        .map(|h| Hailstone {
+            position: sub(h.position, origin.position),
+            velocity: sub(h.velocity, origin.velocity),
+        })
+        .collect_vec();
 
-    (Solution::from(part_a("")), Solution::from(part_b("")))
+    // Find two hailstones whose planes aren't the same
+    let direction = relative
+        .iter()
+        .tuple_combinations()
+        .find_map(|(a, b)| {
+            let normal_a = cross(a.position, a.velocity);
+            let normal_b = cross(b.position, b.velocity);
+            let d = cross(normal_a, normal_b);
+            (d != [0, 0, 0]).then_some(d)
+        })
+        .expect("every hailstone lies in the same plane");
+
+    // Shrink the direction back down so the next cross products can't overflow
+    let divisor = gcd_i128(gcd_i128(direction[0], direction[1]), direction[2]);
+    let direction = direction.map(|x| x / divisor);
+
+    // Hailstone h is on the line when (p + t * v) x d = 0, so t = -(p x d) / (v x d) using any non-zero component
+    let crossing = |h: &Hailstone| -> (i128, Vector) {
+        let (pd, vd) = (cross(h.position, direction), cross(h.velocity, direction));
+        let i = (0..3)
+            .find(|&i| vd[i] != 0)
+            .expect("hailstone never meets the rock");
+        assert_eq!(
+            pd[i] % vd[i],
+            0,
+            "the rock would have to hit at a fractional time"
+        );
+        let t = -pd[i] / vd[i];
+        (t, [0, 1, 2].map(|k| h.position[k] + t * h.velocity[k]))
+    };
+
+    // Two hits at the same moment don't give a speed, so find a pair hit at different times
+    let ((ta, hit_a), (tb, hit_b)) = relative
+        .iter()
+        .map(crossing)
+        .tuple_combinations()
+        .find(|((ta, _), (tb, _))| ta != tb)
+        .expect("every hailstone is hit at the same time");
+
+    // Back out of the first hailstone's frame
+    let velocity = [0, 1, 2].map(|k| {
+        let distance = hit_b[k] - hit_a[k];
+        assert_eq!(
+            distance % (tb - ta),
+            0,
+            "the rock would have to move at a fractional speed"
+        );
+        distance / (tb - ta)
+    });
+    let position = [0, 1, 2].map(|k| hit_a[k] - ta * velocity[k] + origin.position[k]);
+    let velocity = [0, 1, 2].map(|k| velocity[k] + origin.velocity[k]);
+    (position, velocity)
+}
+
+fn part_a(input: &str) -> usize {
+    count_crossings(input, 200_000_000_000_000, 400_000_000_000_000)
+}
+
+fn part_b(input: &str) -> i128 {
+    let hailstones = input.lines().map(Hailstone::parse).collect_vec();
+    let (position, _) = throw_rock(&hailstones);
+    position.iter().sum()
+}
+
+pub fn solve() -> SolutionPair {
+    (
+        Solution::from(part_a(get_input())),
+        Solution::from(part_b(get_input())),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    static TEST_INPUT: &str = "";
+    static TEST_INPUT: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
 
     #[test]
     fn test_part_a() {
-        let result = part_a(TEST_INPUT);
-        assert_eq!(result, 0);
+        let result = count_crossings(TEST_INPUT, 7, 27);
+        assert_eq!(result, 2);
     }
 
     #[test]
     fn test_part_b() {
         let result = part_b(TEST_INPUT);
-        assert_eq!(result, 0);
+        assert_eq!(result, 47);
+    }
+
+    #[test]
+    fn test_throw_rock() {
+        let hailstones = TEST_INPUT.lines().map(Hailstone::parse).collect_vec();
+        assert_eq!(throw_rock(&hailstones), ([24, 13, 10], [-3, 1, 2]));
+
+        // Every hailstone gets hit, some at the same time as each other would be fine too
+        let (p, v) = throw_rock(&hailstones);
+        for h in &hailstones {
+            let offset = sub(h.position, p);
+            let closing = sub(v, h.velocity);
+            assert_eq!(cross(offset, closing), [0, 0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_real_sized_numbers() {
+        // A rock thrown from somewhere at the scale of real inputs, with hailstones placed on its path
+        let (p, v): (Vector, Vector) = (
+            [
+                191_146_615_936_494,
+                342_596_108_503_183,
+                131_079_628_110_881,
+            ],
+            [139, -93, 245],
+        );
+        let hailstones = [
+            (23_143_u64, [-11, 42, 7]),
+            (418_776_u64, [25, -13, 31]),
+            (913_441_u64, [-64, 17, -2]),
+            (602_117_u64, [3, 3, -19]),
+        ]
+        .map(|(t, w): (u64, Vector)| {
+            let t = t as i128;
+            let hit = [0, 1, 2].map(|k| p[k] + t * v[k]);
+            Hailstone {
+                position: [0, 1, 2].map(|k| hit[k] - t * w[k]),
+                velocity: w,
+            }
+        });
+        assert_eq!(throw_rock(&hailstones), (p, v));
     }
 }
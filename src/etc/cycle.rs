@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// The result of walking a state machine until it repeats itself.
+// States 0..prefix are only visited once, after that the states prefix..prefix + length loop forever
+pub struct Cycle<S> {
+    pub prefix: usize,
+    pub length: usize,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    // Look up the state after n steps without having to simulate them
+    #[allow(dead_code)]
+    pub fn state_at(&self, n: usize) -> &S {
+        if n < self.states.len() {
+            return &self.states[n];
+        }
+        &self.states[self.prefix + (n - self.prefix) % self.length]
+    }
+
+    // Every distinct state seen, in the order they were first visited
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+// Keep applying step to the state until we see one we've already had, recording the order they were seen in
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle<S>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Cycle {
+                prefix: start,
+                length: states.len() - start,
+                states,
+            };
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+// Find the state after n steps, stopping early if we get there before the cycle closes
+pub fn fast_forward<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut state = initial;
+
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            // We're looping, so jump straight to the equivalent position within the cycle
+            let length = i - start;
+            return states.swap_remove(start + (n - start) % length);
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn step(x: &u32) -> u32 {
+        if *x == 5 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = find_cycle(0, step);
+        assert_eq!((cycle.prefix, cycle.length), (3, 3));
+        assert_eq!(cycle.states(), &[0, 1, 2, 3, 4, 5]);
+
+        // Compare against just running the steps
+        let mut state = 0;
+        for n in 0..50 {
+            assert_eq!(*cycle.state_at(n), state);
+            state = step(&state);
+        }
+        assert_eq!(*cycle.state_at(1_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        assert_eq!(fast_forward(0, step, 0), 0);
+        assert_eq!(fast_forward(0, step, 2), 2);
+        assert_eq!(fast_forward(0, step, 6), 3);
+        assert_eq!(fast_forward(0, step, 1_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_no_prefix() {
+        let cycle = find_cycle(0_u8, |x| (x + 1) % 4);
+        assert_eq!((cycle.prefix, cycle.length), (0, 4));
+        assert_eq!(*cycle.state_at(10), 2);
+    }
+}
@@ -0,0 +1,303 @@
+use std::borrow::Borrow;
+use std::ops::RangeInclusive;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    // Euclid's algorithm, gcd(0, 0) is left as 0
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// The same for signed values that may not fit in a u64, the result is never negative
+pub fn gcd_i128(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+#[allow(dead_code)]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    checked_lcm(a, b).expect("lcm overflowed u64")
+}
+
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    // Divide first to keep the intermediate value as small as possible
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// These take anything iterable, so both slices (&[u64]) and iterators of u64 work
+#[allow(dead_code)]
+pub fn gcd_all<I>(values: I) -> u64
+where
+    I: IntoIterator,
+    I::Item: Borrow<u64>,
+{
+    values.into_iter().fold(0, |acc, x| gcd(acc, *x.borrow()))
+}
+
+pub fn lcm_all<I>(values: I) -> u64
+where
+    I: IntoIterator,
+    I::Item: Borrow<u64>,
+{
+    checked_lcm_all(values).expect("lcm overflowed u64")
+}
+
+pub fn checked_lcm_all<I>(values: I) -> Option<u64>
+where
+    I: IntoIterator,
+    I::Item: Borrow<u64>,
+{
+    values
+        .into_iter()
+        .try_fold(1, |acc, x| checked_lcm(acc, *x.borrow()))
+}
+
+fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        // Keep the gcd positive regardless of the sign of the inputs
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (g, x, y) = egcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+// Returns (g, x, y) such that a * x + b * y == g == gcd(a, b)
+#[allow(dead_code)]
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = egcd(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+// The x for which a * x == 1 (mod m), if there is one
+#[allow(dead_code)]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = egcd(a as i128, m as i128);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m as i128) as i64)
+}
+
+#[allow(dead_code)]
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    // Square and multiply, going through u128 so the products can't overflow
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+// Why a set of congruences has no answer that crt can give
+#[derive(Debug, PartialEq)]
+pub enum CrtError {
+    // No x satisfies all of them
+    Contradiction,
+    // There is an answer, but the combined modulus doesn't fit in an i64
+    Overflow,
+}
+
+// Solve x == a (mod m) for every (a, m) pair, returning (x, lcm of the moduli) with x as the smallest non-negative answer.
+// The moduli don't have to be coprime
+pub fn crt(congruences: &[(i64, i64)]) -> Result<(i64, i64), CrtError> {
+    let mut result: (i128, i128) = (0, 1);
+
+    for &(a, m) in congruences {
+        assert!(m > 0, "crt moduli must be positive");
+        let (r, n) = result;
+        let (a, m) = ((a as i128).rem_euclid(m as i128), m as i128);
+
+        // r + n * k == a (mod m) only has a solution if gcd(n, m) divides the difference
+        let (g, p, _) = egcd(n, m);
+        let diff = a - r;
+        if diff % g != 0 {
+            return Err(CrtError::Contradiction);
+        }
+
+        let k = (diff / g * p).rem_euclid(m / g);
+        let modulus = n / g * m;
+        if modulus > i64::MAX as i128 {
+            return Err(CrtError::Overflow);
+        }
+        result = ((r + n * k).rem_euclid(modulus), modulus);
+    }
+
+    Ok((result.0 as i64, result.1 as i64))
+}
+
+// The largest x with x * x <= n, without any float rounding
+#[allow(dead_code)]
+pub fn isqrt(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
+
+// The same for u128, for values like discriminants that can be far bigger than a u64
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Floats only get the top 53 bits right, so take a Newton step from there which always lands on or above the
+    // root, then keep stepping down until it's exact
+    let mut x = (n as f64).sqrt() as u128;
+    x = (x + n / x) / 2;
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+    while x.checked_mul(x).is_none_or(|sq| sq > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_mul(x + 1).is_some_and(|sq| sq <= n) {
+        x += 1;
+    }
+    x
+}
+
+// The range of integers x for which a * x^2 + b * x + c < 0, given a > 0.
+// The ends are found with an integer square root and then checked exactly, so there's no precision loss on big inputs.
+// Everything is worked out in i128 with checked arithmetic, any i64 inputs work as long as the roots fit in an i64
+pub fn quadratic_below_zero(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    assert!(a > 0, "the quadratic must open upwards");
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let overflow = "quadratic_below_zero overflowed i128";
+    let eval = |x: i128| {
+        a.checked_mul(x)
+            .and_then(|ax| ax.checked_add(b))
+            .and_then(|axb| axb.checked_mul(x))
+            .and_then(|axbx| axbx.checked_add(c))
+            .expect(overflow)
+    };
+
+    // b^2 fits for any i64 but 4ac can need 129 bits
+    let discriminant = (4 * a)
+        .checked_mul(c)
+        .and_then(|ac| (b * b).checked_sub(ac))
+        .expect(overflow);
+    if discriminant <= 0 {
+        return None;
+    }
+    let root = isqrt_u128(discriminant as u128) as i128;
+
+    // Start from the approximate roots and move inwards/outwards until the ends are exact
+    let mut low = (-b - root).div_euclid(2 * a);
+    let mut high = (-b + root).div_euclid(2 * a) + 1;
+    while eval(low) >= 0 && low <= high {
+        low += 1;
+    }
+    while eval(low - 1) < 0 {
+        low -= 1;
+    }
+    while eval(high) >= 0 && high >= low {
+        high -= 1;
+    }
+    while eval(high + 1) < 0 {
+        high += 1;
+    }
+
+    if low > high {
+        return None;
+    }
+    let to_i64 = |x: i128| i64::try_from(x).expect("quadratic root doesn't fit in an i64");
+    Some(to_i64(low)..=to_i64(high))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd_i128(-12, 18), 6);
+        assert_eq!(gcd_i128(12, -18), 6);
+        assert_eq!(gcd_i128(0, -7), 7);
+        assert_eq!(gcd_i128(3 << 100, 9 << 90), 3 << 90);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        let values: &[u64] = &[2, 3, 4, 5];
+        assert_eq!(lcm_all(values), 60);
+        assert_eq!(lcm_all((1..=10).map(|x| x as u64)), 2520);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(checked_lcm_all([1 << 40, 3 << 30, 5 << 30]), Some(15 << 40));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(2, 62, 1_000_000_007), (1_u64 << 62) % 1_000_000_007);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        // Moduli sharing a factor
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), Err(CrtError::Contradiction));
+        // Negative residues are normalised
+        assert_eq!(crt(&[(-1, 5), (0, 3)]), Ok((9, 15)));
+        // Consecutive numbers are coprime, so these combine to a modulus around 2^126
+        assert_eq!(
+            crt(&[(0, i64::MAX), (1, i64::MAX - 1)]),
+            Err(CrtError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n);
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt_u128(1 << 100), 1 << 50);
+        assert_eq!(isqrt_u128((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn test_quadratic_below_zero() {
+        // (x - 2)(x - 5) = x^2 - 7x + 10
+        assert_eq!(quadratic_below_zero(1, -7, 10), Some(3..=4));
+        assert_eq!(quadratic_below_zero(1, 0, 1), None);
+        assert_eq!(quadratic_below_zero(1, -2, 1), None);
+        // The discriminant here is 10^20, which is bigger than u64::MAX
+        assert_eq!(
+            quadratic_below_zero(1, -10_000_000_000, 0),
+            Some(1..=9_999_999_999)
+        );
+        assert_eq!(
+            quadratic_below_zero(1, i64::MIN + 1, 0),
+            Some(1..=i64::MAX - 1)
+        );
+        // Compare with brute force
+        for b in -20..20 {
+            for c in -20..20 {
+                let expected = (-50..50)
+                    .filter(|x| x * x + b * x + c < 0)
+                    .collect::<Vec<_>>();
+                let found = quadratic_below_zero(1, b, c).map_or(vec![], |r| r.collect());
+                assert_eq!(found, expected);
+            }
+        }
+    }
+}
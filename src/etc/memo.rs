@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries",
+            self.hits, self.misses, self.entries
+        )
+    }
+}
+
+// A cache for a recursive function. Create one per solver run so nothing is shared between runs.
+// Keys can borrow from the input (e.g. (&[u8], &[usize])) as long as the memo doesn't outlive it
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: CacheStats,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    // Return the cached value for the key, or work it out with f and remember it.
+    // f gets the memo back so it can recurse, e.g. memo.get_or_insert_with(key, |memo| count(memo, ...))
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    #[allow(dead_code)]
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.cache.len(),
+            ..self.stats
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = CacheStats::default();
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    // Ways to make a total out of the given coins, keyed on a borrowed slice
+    fn change<'a>(memo: &mut Memo<(u64, &'a [u64]), u64>, total: u64, coins: &'a [u64]) -> u64 {
+        if total == 0 {
+            return 1;
+        }
+        let Some((&coin, rest)) = coins.split_first() else {
+            return 0;
+        };
+        memo.get_or_insert_with((total, coins), |memo| {
+            let without = change(memo, total, rest);
+            if coin > total {
+                return without;
+            }
+            without + change(memo, total - coin, coins)
+        })
+    }
+
+    #[test]
+    fn test_fib() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(
+            memo.stats(),
+            CacheStats {
+                hits: 87,
+                misses: 89,
+                entries: 89
+            }
+        );
+
+        // A fresh memo starts from nothing
+        let mut other = Memo::new();
+        assert_eq!(fib(&mut other, 10), 55);
+        assert_eq!(other.stats().entries, 9);
+
+        memo.clear();
+        assert_eq!(memo.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let coins = vec![50, 25, 10, 5, 1];
+        let mut memo = Memo::new();
+        assert_eq!(change(&mut memo, 100, &coins), 292);
+        assert!(memo.stats().hits > 0);
+
+        let stats = CacheStats {
+            hits: 3,
+            misses: 2,
+            entries: 2,
+        };
+        assert_eq!(stats.to_string(), "3 hits, 2 misses, 2 entries");
+    }
+}
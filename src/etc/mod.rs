@@ -1,3 +1,8 @@
+pub mod cycle;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod polygon;
 pub mod solution;
 
-pub use solution::Solution;
\ No newline at end of file
+pub use solution::Solution;
@@ -0,0 +1,226 @@
+use grid::Grid;
+use nom::{
+    bytes::complete::{tag, take_till1},
+    character::complete::{alphanumeric1, i64, line_ending, multispace0, none_of, space0, space1},
+    combinator::{all_consuming, verify},
+    error::{Error, ErrorKind},
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Finish, IResult, Parser,
+};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+// Where and why parsing an input went wrong, lines and columns both start at 1
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    // Work out the position of the remaining input within the whole input
+    fn new(input: &str, remaining: &str, message: String) -> Self {
+        let offset = input.len() - remaining.len();
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message,
+        }
+    }
+}
+
+// Run a parser over the whole input, allowing trailing whitespace, and turn any failure into a ParseError
+pub fn parse_all<'a, O, F>(input: &'a str, parser: F) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|e| {
+            let found = found(e.input);
+            let message = match e.code {
+                ErrorKind::Eof => format!("unexpected trailing input {found}"),
+                code => format!(
+                    "expected {}, found {found}",
+                    code.description().to_lowercase()
+                ),
+            };
+            ParseError::new(input, e.input, message)
+        })
+}
+
+// A short quote of the text a parser stopped at, up to the end of its line
+fn found(remaining: &str) -> String {
+    let line = remaining.lines().next().unwrap_or("");
+    if line.is_empty() {
+        return if remaining.is_empty() {
+            "end of input".to_owned()
+        } else {
+            "end of line".to_owned()
+        };
+    }
+    let quote: String = line.chars().take(20).collect();
+    let ellipsis = if quote.len() < line.len() { "..." } else { "" };
+    format!("{quote:?}{ellipsis}")
+}
+
+// A single signed integer
+#[allow(dead_code)]
+pub fn int(input: &str) -> IResult<&str, i64> {
+    i64(input)
+}
+
+// Signed integers with a separator between them, e.g. "1, -2, 3" with ","
+pub fn int_list<'a>(sep: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<i64>> {
+    separated_list1(delimited(space0, tag(sep), space0), i64)
+}
+
+// A row of numbers split by any amount of spaces, e.g. " 41 48  83 -86"
+pub fn number_row(input: &str) -> IResult<&str, Vec<i64>> {
+    preceded(space0, separated_list1(space1, i64))(input)
+}
+
+// A "label: values" line, returning the label and whatever the values parser produced
+pub fn labelled<'a, O, F>(values: F) -> impl FnMut(&'a str) -> IResult<&'a str, (&'a str, O)>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_pair(
+        take_till1(|c| c == ':' || c == '\n'),
+        pair(tag(":"), space0),
+        values,
+    )
+}
+
+// One parser applied to each line
+pub fn lines<'a, O, F>(line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_ending, line)
+}
+
+// Blocks of input separated by blank lines
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+// A "key = (left, right)" line
+pub fn key_pair(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        alphanumeric1,
+        tuple((space0, tag("="), space0)),
+        delimited(
+            tag("("),
+            separated_pair(alphanumeric1, pair(tag(","), space0), alphanumeric1),
+            tag(")"),
+        ),
+    )(input)
+}
+
+// Lines of "key = (left, right)" collected into a map
+pub fn key_pair_map(input: &str) -> IResult<&str, HashMap<&str, (&str, &str)>> {
+    let (input, pairs) = lines(key_pair)(input)?;
+    Ok((input, pairs.into_iter().collect()))
+}
+
+// A rectangle of characters, every row has to be the same width
+pub fn grid(input: &str) -> IResult<&str, Grid<char>> {
+    let (input, rows) = verify(lines(many1(none_of("\r\n"))), |rows: &Vec<Vec<char>>| {
+        rows.iter().all(|row| row.len() == rows[0].len())
+    })(input)?;
+
+    let cols = rows[0].len();
+    Ok((input, Grid::from_vec(rows.concat(), cols)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse_all("1, -2,3", int_list(",")), Ok(vec![1, -2, 3]));
+        assert_eq!(
+            parse_all(" 41 48  83 -86\n", number_row),
+            Ok(vec![41, 48, 83, -86])
+        );
+        assert_eq!(parse_all("-12", int), Ok(-12));
+    }
+
+    #[test]
+    fn test_labelled_blocks() {
+        let input = "seeds: 79 14 55 13\n\nTime:      7  15   30\nDistance:  9  40  200\n";
+        let (seeds, rest) = input.split_once("\n\n").unwrap();
+        assert_eq!(
+            parse_all(seeds, labelled(number_row)),
+            Ok(("seeds", vec![79, 14, 55, 13]))
+        );
+        assert_eq!(
+            parse_all(rest, lines(labelled(number_row))),
+            Ok(vec![
+                ("Time", vec![7, 15, 30]),
+                ("Distance", vec![9, 40, 200])
+            ])
+        );
+
+        let blocks = parse_all("#.\n.#\n\n##\n..\n..", blocks(grid)).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[1].rows(), blocks[1].cols()), (3, 2));
+    }
+
+    #[test]
+    fn test_key_pair_map() {
+        let map = parse_all("AAA = (BBB, CCC)\r\nBBB = (DDD, EEE)\r\n", key_pair_map).unwrap();
+        assert_eq!(map["AAA"], ("BBB", "CCC"));
+        assert_eq!(map["BBB"], ("DDD", "EEE"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = parse_all("1 2 3\n4 x 6", lines(number_row)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: unexpected trailing input \"x 6\""
+        );
+
+        let err = parse_all("AAA = (BBB CCC)", key_pair).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 11));
+        assert_eq!(err.message, "expected tag, found \" CCC)\"");
+
+        // Long lines are cut short, and running out of input says so
+        let err = parse_all("1, 2, three hundred and forty five", int_list(",")).unwrap_err();
+        assert_eq!(
+            err.message,
+            "unexpected trailing input \", three hundred and \"..."
+        );
+        let err = parse_all("", number_row).unwrap_err();
+        assert_eq!(err.message, "expected digit, found end of input");
+
+        // Ragged grids are rejected at the start of the grid
+        let err = parse_all("...\n..\n...", grid).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
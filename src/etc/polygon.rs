@@ -0,0 +1,251 @@
+use crate::etc::math::gcd;
+
+// Polygons are given as a list of (x, y) vertices in order, the last vertex joins back up to the first
+
+// Turn a list of (direction, distance) moves into the vertices visited, starting from the origin.
+// Directions are unit steps such as (1, 0) for right
+pub fn vertices_from_moves<I>(moves: I) -> Vec<(i64, i64)>
+where
+    I: IntoIterator<Item = ((i64, i64), i64)>,
+{
+    let mut position = (0, 0);
+    let mut vertices = vec![];
+
+    for ((dx, dy), distance) in moves {
+        vertices.push(position);
+        position = (position.0 + dx * distance, position.1 + dy * distance);
+    }
+
+    vertices
+}
+
+fn edges(vertices: &[(i64, i64)]) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+// Shoelace formula. Twice the area is always a whole number for integer vertices so this stays exact
+pub fn twice_area(vertices: &[(i64, i64)]) -> i128 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 as i128 * y2 as i128 - x2 as i128 * y1 as i128)
+        .sum::<i128>()
+        .abs()
+}
+
+// The area, rounded down for polygons with half-unit areas
+#[allow(dead_code)]
+pub fn area(vertices: &[(i64, i64)]) -> i64 {
+    i64::try_from(twice_area(vertices) / 2).expect("area doesn't fit in an i64")
+}
+
+// Number of integer points lying on the edges
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| gcd(x1.abs_diff(x2), y1.abs_diff(y2)) as i64)
+        .sum()
+}
+
+// Pick's theorem, A = I + B / 2 - 1, rearranged for the number of integer points strictly inside
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    let interior = (twice_area(vertices) - boundary_points(vertices) as i128 + 2) / 2;
+    i64::try_from(interior).expect("interior point count doesn't fit in an i64")
+}
+
+// Every integer point inside or on the edge, e.g. the number of tiles dug out when the edges are trenches
+pub fn lattice_points(vertices: &[(i64, i64)]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+// Cross product of (b - a) and (p - a), positive if p is to the left of the line a -> b.
+// Everything is widened before subtracting, as the differences alone can overflow an i64
+#[allow(dead_code)]
+fn cross(a: (i64, i64), b: (i64, i64), p: (i64, i64)) -> i128 {
+    let [ax, ay, bx, by, px, py] = [a.0, a.1, b.0, b.1, p.0, p.1].map(|v| v as i128);
+    (bx - ax) * (py - ay) - (by - ay) * (px - ax)
+}
+
+#[allow(dead_code)]
+pub fn on_boundary(point: (i64, i64), vertices: &[(i64, i64)]) -> bool {
+    edges(vertices).any(|(a, b)| {
+        cross(a, b, point) == 0
+            && point.0 >= a.0.min(b.0)
+            && point.0 <= a.0.max(b.0)
+            && point.1 >= a.1.min(b.1)
+            && point.1 <= a.1.max(b.1)
+    })
+}
+
+// How many times the polygon wraps around the point, counter-clockwise positive.
+// Points on the boundary give whatever side the crossing test lands on, use on_boundary to check for them first
+#[allow(dead_code)]
+pub fn winding_number(point: (i64, i64), vertices: &[(i64, i64)]) -> i32 {
+    let mut winding = 0;
+
+    for (a, b) in edges(vertices) {
+        if a.1 <= point.1 {
+            // Upward crossing with the point to the left
+            if b.1 > point.1 && cross(a, b, point) > 0 {
+                winding += 1;
+            }
+        } else if b.1 <= point.1 && cross(a, b, point) < 0 {
+            // Downward crossing with the point to the right
+            winding -= 1;
+        }
+    }
+
+    winding
+}
+
+// Strictly inside, points on the boundary don't count
+#[allow(dead_code)]
+pub fn contains(point: (i64, i64), vertices: &[(i64, i64)]) -> bool {
+    !on_boundary(point, vertices) && winding_number(point, vertices) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashSet, VecDeque};
+
+    const RIGHT: (i64, i64) = (1, 0);
+    const LEFT: (i64, i64) = (-1, 0);
+    const UP: (i64, i64) = (0, -1);
+    const DOWN: (i64, i64) = (0, 1);
+
+    // The dig plan from the day 18 example
+    fn lagoon() -> Vec<(i64, i64)> {
+        vertices_from_moves([
+            (RIGHT, 6),
+            (DOWN, 5),
+            (LEFT, 2),
+            (DOWN, 2),
+            (RIGHT, 2),
+            (DOWN, 2),
+            (LEFT, 5),
+            (UP, 2),
+            (LEFT, 1),
+            (UP, 2),
+            (RIGHT, 2),
+            (UP, 3),
+            (LEFT, 2),
+            (UP, 2),
+        ])
+    }
+
+    // A spiral-ish shape with a narrow corridor
+    fn corridor() -> Vec<(i64, i64)> {
+        vertices_from_moves([
+            (RIGHT, 8),
+            (DOWN, 6),
+            (LEFT, 3),
+            (UP, 4),
+            (LEFT, 1),
+            (DOWN, 4),
+            (LEFT, 4),
+            (UP, 6),
+        ])
+    }
+
+    // Trace the edges into a set of points and flood fill from outside to count (boundary, interior)
+    fn flood_fill(vertices: &[(i64, i64)]) -> (usize, usize) {
+        let mut boundary = HashSet::new();
+        for (a, b) in edges(vertices) {
+            let (dx, dy) = ((b.0 - a.0).signum(), (b.1 - a.1).signum());
+            let mut p = a;
+            while p != b {
+                boundary.insert(p);
+                p = (p.0 + dx, p.1 + dy);
+            }
+        }
+
+        let min_x = vertices.iter().map(|v| v.0).min().unwrap() - 1;
+        let max_x = vertices.iter().map(|v| v.0).max().unwrap() + 1;
+        let min_y = vertices.iter().map(|v| v.1).min().unwrap() - 1;
+        let max_y = vertices.iter().map(|v| v.1).max().unwrap() + 1;
+
+        let mut outside = HashSet::from([(min_x, min_y)]);
+        let mut queue = VecDeque::from([(min_x, min_y)]);
+        while let Some((x, y)) = queue.pop_front() {
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if next.0 < min_x || next.0 > max_x || next.1 < min_y || next.1 > max_y {
+                    continue;
+                }
+                if !boundary.contains(&next) && outside.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let total = ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize;
+        (boundary.len(), total - outside.len() - boundary.len())
+    }
+
+    #[test]
+    fn test_against_flood_fill() {
+        for vertices in [lagoon(), corridor()] {
+            let (boundary, interior) = flood_fill(&vertices);
+            assert_eq!(boundary_points(&vertices), boundary as i64);
+            assert_eq!(interior_points(&vertices), interior as i64);
+
+            // Check every point individually with the winding number
+            let inside = (-2..12)
+                .flat_map(|x| (-2..12).map(move |y| (x, y)))
+                .filter(|&p| contains(p, &vertices))
+                .count();
+            assert_eq!(inside, interior);
+        }
+        assert_eq!(lattice_points(&lagoon()), 62);
+    }
+
+    #[test]
+    fn test_area() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(area(&square), 16);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        // A triangle with a diagonal edge
+        let triangle = [(0, 0), (4, 0), (0, 4)];
+        assert_eq!(twice_area(&triangle), 16);
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+
+        // Big enough that the intermediate products don't fit in an i64
+        let huge = [
+            (0, 0),
+            (3_000_000_000, 0),
+            (3_000_000_000, 3_000_000_000),
+            (0, 3_000_000_000),
+        ];
+        assert_eq!(area(&huge), 9_000_000_000_000_000_000);
+        assert_eq!(lattice_points(&huge), 3_000_000_001 * 3_000_000_001);
+    }
+
+    #[test]
+    fn test_winding() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(winding_number((2, 2), &square).abs(), 1);
+        assert_eq!(winding_number((5, 2), &square), 0);
+        assert!(on_boundary((4, 2), &square));
+        assert!(!contains((4, 2), &square));
+        assert!(contains((1, 3), &square));
+
+        // Coordinates so far apart that their differences don't fit in an i64
+        let far = 5_000_000_000_000_000_000;
+        let huge = [(-far, -far), (far, -far), (far, far), (-far, far)];
+        assert!(contains((0, 0), &huge));
+        assert!(contains((far - 1, 1 - far), &huge));
+        assert!(on_boundary((far, 0), &huge));
+        assert!(!contains((far, 0), &huge));
+        assert_eq!(winding_number((i64::MIN, 0), &huge), 0);
+
+        // Reversing the direction flips the sign
+        let reversed: Vec<_> = square.iter().rev().copied().collect();
+        assert_eq!(
+            winding_number((2, 2), &square),
+            -winding_number((2, 2), &reversed)
+        );
+    }
+}